@@ -0,0 +1,175 @@
+use std::{
+    io, panic,
+    time::{Duration, Instant},
+};
+
+use common::{
+    arith::Arithmetic, cli::Format, error_json, json::Json, options::Options, Answer, Day, Error,
+    Outcome,
+};
+
+use crate::{pool, read_input};
+
+enum Failure {
+    Input(io::Error),
+    Solve(Error),
+    Panic(String),
+}
+
+struct Report {
+    day: u8,
+    time: Option<Duration>,
+    result: Result<Outcome, Failure>,
+}
+
+impl Report {
+    fn error(&self) -> Option<String> {
+        match self.result.as_ref().err()? {
+            Failure::Input(err) => Some(format!("cannot read day{}/input: {}", self.day, err)),
+            Failure::Solve(err) => Some(err.to_string()),
+            Failure::Panic(message) => Some(format!("panicked: {}", message)),
+        }
+    }
+
+    fn answer(&self, part: u8) -> String {
+        let outcome = match &self.result {
+            Ok(outcome) => outcome,
+            Err(_) => return String::from("-"),
+        };
+        match outcome.parts.iter().find(|p| p.part == part) {
+            Some(p) => match &p.answer {
+                Answer::Text(text) if text.contains('\n') => {
+                    format!("({} lines)", text.lines().count())
+                }
+                answer => answer.to_string(),
+            },
+            None => String::from("-"),
+        }
+    }
+
+    fn to_json(&self) -> Json {
+        let day = ("day", Json::number(self.day));
+        match &self.result {
+            Ok(outcome) => outcome.to_json(),
+            Err(Failure::Solve(err)) => error_json(self.day, err),
+            Err(Failure::Input(err)) => {
+                Json::Object(vec![day, ("input_error", Json::string(err.to_string()))])
+            }
+            Err(Failure::Panic(message)) => {
+                Json::Object(vec![day, ("panic", Json::string(message.as_str()))])
+            }
+        }
+    }
+}
+
+fn print_table(reports: &[Report], elapsed: Duration, jobs: usize) {
+    let rows = reports
+        .iter()
+        .map(|report| {
+            [
+                report.day.to_string(),
+                report.answer(1),
+                report.answer(2),
+                report
+                    .time
+                    .map(|time| format!("{:.2?}", time))
+                    .unwrap_or_else(|| String::from("-")),
+                report.error().unwrap_or_default(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let header = ["Day", "Part 1", "Part 2", "Time", "Error"];
+    let widths = (0..header.len())
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .chain([header[col].len()])
+                .max()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    for row in [header.map(String::from)].iter().chain(rows.iter()) {
+        let line = format!(
+            "{:>w0$}  {:<w1$}  {:<w2$}  {:>w3$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        );
+        println!("{}", line.trim_end());
+    }
+
+    let failed = reports
+        .iter()
+        .filter(|report| report.result.is_err())
+        .count();
+    println!(
+        "{} days in {:.2?} on {} threads, {} failed",
+        reports.len(),
+        elapsed,
+        jobs,
+        failed
+    );
+}
+
+/// Runs `days` on at most `jobs` threads, keeping a failure in one day from
+/// affecting the rest, and prints a single report once they are all done.
+/// Returns whether every day succeeded.
+pub fn run(
+    days: &[&'static Day],
+    parts: &[u8],
+    example: bool,
+    format: Format,
+    arithmetic: Arithmetic,
+    options: &Options,
+    jobs: usize,
+) -> bool {
+    // panics are reported in the table, not as they happen
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let start = Instant::now();
+    let results = pool::map(jobs, days, |day| {
+        let start = Instant::now();
+        let result = read_input(day, example)
+            .map_err(Failure::Input)
+            .and_then(|input| {
+                day.run_with(&input, parts, arithmetic, options)
+                    .map_err(Failure::Solve)
+            });
+        (result, start.elapsed())
+    });
+    let elapsed = start.elapsed();
+    panic::set_hook(hook);
+
+    let reports = days
+        .iter()
+        .zip(results)
+        .map(|(day, result)| match result {
+            Ok((result, time)) => Report {
+                day: day.number,
+                time: Some(time),
+                result,
+            },
+            Err(message) => Report {
+                day: day.number,
+                time: None,
+                result: Err(Failure::Panic(message)),
+            },
+        })
+        .collect::<Vec<_>>();
+
+    match format {
+        Format::Text => print_table(&reports, elapsed, jobs.clamp(1, days.len().max(1))),
+        Format::Json => println!(
+            "{}",
+            Json::Array(reports.iter().map(Report::to_json).collect())
+        ),
+    }
+    reports.iter().all(|report| report.result.is_ok())
+}
@@ -0,0 +1,80 @@
+use std::{
+    process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use common::{
+    cli::Args,
+    generate::{Generated, Rng},
+    Answer, Day,
+};
+
+use crate::{select_days, usage};
+
+/// Solves a generated input and reports how it compares with the known
+/// answers.
+fn check_day(day: &Day, generated: &Generated) -> bool {
+    println!("Day {}", day.number);
+    let outcome = match day.run(&generated.input, &[1, 2]) {
+        Ok(outcome) => outcome,
+        Err(err) => {
+            println!("  error: {}", err);
+            return false;
+        }
+    };
+
+    let mismatches = generated.mismatches(&outcome);
+    for part in outcome.parts.iter() {
+        let status = match generated.expected(part.part) {
+            None => "no known answer",
+            Some(_) if mismatches.iter().any(|m| m.0 == part.part) => "FAIL",
+            Some(_) => "pass",
+        };
+        println!("  Part {}: {} ({:.2?})", part.part, status, part.time);
+    }
+    for (part, expected, actual) in mismatches.iter() {
+        println!("  Part {}: expected {}, got {}", part, expected, actual);
+    }
+    mismatches.is_empty()
+}
+
+pub fn generate(mut args: Args) {
+    let seed = args.parsed_option::<u64>("--seed").unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0)
+    });
+    let size = args.parsed_option::<usize>("--size");
+    let check = args.flag("--check");
+    let days = select_days(&args.positional().unwrap_or_else(|| usage()));
+    args.finish();
+    if days.len() > 1 && !check {
+        usage();
+    }
+
+    eprintln!("seed {}", seed);
+    let mut failed = false;
+    for day in days {
+        let mut rng = Rng::new(seed);
+        let generated = day.generate(&mut rng, size.unwrap_or(day.default_size));
+        if check {
+            failed |= !check_day(day, &generated);
+            continue;
+        }
+
+        print!("{}", generated.input);
+        for part in 1..=2 {
+            match generated.expected(part) {
+                Some(Answer::Text(art)) if art.contains('\n') => {
+                    eprint!("Part {}:\n{}", part, art)
+                }
+                Some(answer) => eprintln!("Part {}: {}", part, answer),
+                None => {}
+            }
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+
+use common::{ParseError, Span};
+
+const HEADER: &str =
+    "# Known puzzle answers, checked by `aoc verify` and updated by `aoc verify --record`.\n";
+
+/// Answers keyed by day and part, stored as a small subset of TOML: one
+/// `[dayN]` table per day with `partN` keys holding integers or strings.
+#[derive(Default)]
+pub struct Ledger {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+impl Ledger {
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn set(&mut self, day: u8, part: u8, answer: String) {
+        self.answers.insert((day, part), answer);
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut ledger = Ledger::default();
+        let mut day = None;
+
+        for line in Span::new(input).lines() {
+            let line = line.trim();
+            if line.is_empty() || line.as_str().starts_with('#') {
+                continue;
+            }
+            if let Some(table) = line.strip_prefix("[day") {
+                let (number, rest) = table
+                    .split_once("]")
+                    .ok_or_else(|| table.end().error("closing `]`"))?;
+                if !rest.is_empty() {
+                    return Err(rest.error("end of line"));
+                }
+                day = Some(number.parse::<u8>("day number")?);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once("=")
+                .ok_or_else(|| line.error("`[dayN]` table or `partN = answer`"))?;
+            let key = key.trim();
+            let part = key
+                .strip_prefix("part")
+                .ok_or_else(|| key.error("`part1` or `part2` key"))?
+                .parse::<u8>("part number")?;
+            let day = day.ok_or_else(|| key.error("`[dayN]` table before answers"))?;
+            ledger.set(day, part, parse_value(value.trim())?);
+        }
+
+        Ok(ledger)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut toml = String::from(HEADER);
+        let mut current_day = None;
+        for (&(day, part), answer) in self.answers.iter() {
+            if current_day != Some(day) {
+                toml.push_str(&format!("\n[day{}]\n", day));
+                current_day = Some(day);
+            }
+            toml.push_str(&format!("part{} = {}\n", part, format_value(answer)));
+        }
+        toml
+    }
+}
+
+fn is_integer(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_digit())
+}
+
+fn parse_value(value: Span) -> Result<String, ParseError> {
+    if is_integer(value.as_str()) {
+        return Ok(String::from(value.as_str()));
+    }
+
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(('"', _)) => {}
+        _ => return Err(value.error("integer or quoted string")),
+    }
+    let mut answer = String::new();
+    loop {
+        match chars.next() {
+            Some(('"', _)) => break,
+            Some(('\\', escape)) => match chars.next() {
+                Some(('n', _)) => answer.push('\n'),
+                Some(('t', _)) => answer.push('\t'),
+                Some(('"', _)) => answer.push('"'),
+                Some(('\\', _)) => answer.push('\\'),
+                _ => return Err(escape.error("one of `\\n`, `\\t`, `\\\"` or `\\\\`")),
+            },
+            Some((c, _)) => answer.push(c),
+            None => return Err(value.end().error("closing quote")),
+        }
+    }
+    match chars.next() {
+        Some((_, rest)) => Err(rest.error("end of line")),
+        None => Ok(answer),
+    }
+}
+
+fn format_value(answer: &str) -> String {
+    if is_integer(answer) {
+        return String::from(answer);
+    }
+
+    let mut value = String::from("\"");
+    for c in answer.chars() {
+        match c {
+            '\n' => value.push_str("\\n"),
+            '\t' => value.push_str("\\t"),
+            '"' => value.push_str("\\\""),
+            '\\' => value.push_str("\\\\"),
+            _ => value.push(c),
+        }
+    }
+    value.push('"');
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut ledger = Ledger::default();
+        ledger.set(1, 1, String::from("1532"));
+        ledger.set(13, 2, String::from("\n█ \"x\"\\\n"));
+        ledger.set(1, 2, String::from("abc"));
+
+        let toml = ledger.to_toml();
+        assert!(toml.contains("[day1]\npart1 = 1532\npart2 = \"abc\"\n"));
+
+        let parsed = Ledger::parse(&toml).unwrap();
+        assert_eq!(parsed.answers, ledger.answers);
+    }
+
+    #[test]
+    fn reports_position_of_bad_value() {
+        let err = Ledger::parse("[day3]\npart1 = 12x\n").err().unwrap();
+        assert_eq!((err.line, err.column), (2, 9));
+    }
+}
@@ -0,0 +1,198 @@
+use std::{fs::read_to_string, io, process};
+
+use common::{
+    arith::Arithmetic,
+    cli::{Args, Format},
+    error_json,
+    json::Json,
+    options::Options,
+    profile::Counting,
+    trace, Day,
+};
+
+mod batch;
+mod generate;
+mod ledger;
+mod pool;
+mod verify;
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+const DAYS: [Day; 14] = [
+    Day::of::<day1::Day1>(),
+    Day::of::<day2::Day2>(),
+    Day::of::<day3::Day3>(),
+    Day::of::<day4::Day4>(),
+    Day::of::<day5::Day5>(),
+    Day::of::<day6::Day6>(),
+    Day::of::<day7::Day7>(),
+    Day::of::<day8::Day8>(),
+    Day::of::<day9::Day9>(),
+    Day::of::<day10::Day10>(),
+    Day::of::<day11::Day11>(),
+    Day::of::<day12::Day12>(),
+    Day::of::<day13::Day13>(),
+    Day::of::<day14::Day14>(),
+];
+
+const USAGE: &str = "usage: aoc run <day|all> [--part <1|2>] [--example] [--format <text|json>]
+                   [--arith <native|checked|big>] [--jobs <n>] [--trace <filter>]
+                   [--profile] [day options]
+       aoc verify [day|all] [--record] [--answers <file>] [day options]
+       aoc generate <day> [--seed <n>] [--size <n>] > input
+       aoc generate <day|all> --check [--seed <n>] [--size <n>]";
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2)
+}
+
+fn find_day(number: u8) -> &'static Day {
+    DAYS.iter()
+        .find(|day| day.number == number)
+        .unwrap_or_else(|| usage())
+}
+
+/// The options of every day, so `all` can be given any of them.
+fn day_options(args: &mut Args) -> Options {
+    Options::from_args(args, DAYS.iter().flat_map(|day| day.options))
+}
+
+fn select_days(selection: &str) -> Vec<&'static Day> {
+    match selection {
+        "all" => DAYS.iter().collect(),
+        n => vec![find_day(n.parse().unwrap_or_else(|_| usage()))],
+    }
+}
+
+fn read_input(day: &Day, example: bool) -> io::Result<String> {
+    if example {
+        return Ok(String::from(day.example));
+    }
+    read_to_string(format!("day{}/input", day.number))
+}
+
+fn run_day(
+    day: &Day,
+    parts: &[u8],
+    example: bool,
+    format: Format,
+    arithmetic: Arithmetic,
+    options: &Options,
+) -> (bool, Json) {
+    let input = match read_input(day, example) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Cannot read day{}/input: {}", day.number, err);
+            process::exit(1)
+        }
+    };
+
+    let outcome = day.run_with(&input, parts, arithmetic, options);
+    if format == Format::Text {
+        println!("Day {}", day.number);
+        match &outcome {
+            Ok(outcome) => {
+                for warning in outcome.warnings.iter() {
+                    println!("  warning: {}", warning);
+                }
+                for part in outcome.parts.iter() {
+                    for line in part.to_string().lines() {
+                        println!("  {}", line);
+                    }
+                }
+            }
+            Err(err) => println!("  error: {}", err),
+        }
+    }
+
+    match outcome {
+        Ok(outcome) => (true, outcome.to_json()),
+        Err(err) => (false, error_json(day.number, &err)),
+    }
+}
+
+fn profile_days(days: &[&Day], example: bool, format: Format, options: &Options) {
+    let mut failed = false;
+    let mut reports = Vec::new();
+    for day in days {
+        let input = read_input(day, example).unwrap_or_else(|err| {
+            eprintln!("Cannot read day{}/input: {}", day.number, err);
+            process::exit(1)
+        });
+        match (day.profile(&input, options), format) {
+            (Ok(report), Format::Text) => print!("{}", report),
+            (Ok(report), Format::Json) => reports.push(report.to_json()),
+            (Err(err), _) => {
+                failed = true;
+                eprintln!("Day {}: {}", day.number, err);
+            }
+        }
+    }
+    if format == Format::Json {
+        println!("{}", Json::Array(reports));
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn run(mut args: Args) {
+    let parts = match args.option("--part").as_deref() {
+        None => vec![1, 2],
+        Some("1") => vec![1],
+        Some("2") => vec![2],
+        Some(_) => usage(),
+    };
+    let example = args.flag("--example");
+    let format = Format::from_args(&mut args);
+    let arithmetic = Arithmetic::from_args(&mut args);
+    trace::from_args(&mut args, format);
+    let jobs = args.parsed_option::<usize>("--jobs");
+    let profile = args.flag("--profile");
+    let options = day_options(&mut args);
+    let days = select_days(&args.positional().unwrap_or_else(|| usage()));
+    args.finish();
+
+    if profile {
+        // allocation counts are global, so days can't share the process
+        if jobs.is_some() {
+            usage();
+        }
+        return profile_days(&days, example, format, &options);
+    }
+    if let Some(jobs) = jobs {
+        if jobs == 0 {
+            usage();
+        }
+        if !batch::run(&days, &parts, example, format, arithmetic, &options, jobs) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let mut failed = false;
+    let mut results = Vec::new();
+    for day in days {
+        let (ok, json) = run_day(day, &parts, example, format, arithmetic, &options);
+        failed |= !ok;
+        results.push(json);
+    }
+    if format == Format::Json {
+        println!("{}", Json::Array(results));
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn main() {
+    let mut args = Args::from_env();
+    match args.positional().as_deref() {
+        Some("run") => run(args),
+        Some("verify") => verify::verify(args),
+        Some("generate") => generate::generate(args),
+        _ => usage(),
+    }
+}
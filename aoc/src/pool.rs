@@ -0,0 +1,73 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => String::from(*message),
+            Err(_) => String::from("unknown panic"),
+        },
+    }
+}
+
+/// Runs `task` on every item with at most `jobs` threads and returns the
+/// results in the order of `items`. A task that panics doesn't take the
+/// others down; its result is the panic message instead.
+pub fn map<T: Sync, R: Send>(
+    jobs: usize,
+    items: &[T],
+    task: impl Fn(&T) -> R + Sync,
+) -> Vec<Result<R, String>> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(items.iter().map(|_| None).collect::<Vec<_>>());
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let item = match items.get(idx) {
+                    Some(item) => item,
+                    None => break,
+                };
+                let result = panic::catch_unwind(AssertUnwindSafe(|| task(item)));
+                results.lock().unwrap()[idx] = Some(result.map_err(panic_message));
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every item has run"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_order_and_captures_panics() {
+        panic::set_hook(Box::new(|_| {}));
+        let items = (0..20).collect::<Vec<u32>>();
+        let results = map(3, &items, |&n| {
+            if n == 7 {
+                panic!("seven")
+            }
+            n * 2
+        });
+        let _ = panic::take_hook();
+
+        assert_eq!(results.len(), 20);
+        assert_eq!(results[7], Err(String::from("seven")));
+        assert_eq!(results[8], Ok(16));
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 19);
+    }
+}
@@ -0,0 +1,122 @@
+use std::{fs, path::PathBuf, process};
+
+use common::{arith::Arithmetic, cli::Args, options::Options, Day};
+
+use crate::{day_options, ledger::Ledger, read_input, select_days};
+
+enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing { actual: String },
+    Error(String),
+}
+
+fn print_diff(expected: &str, actual: &str) {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+    for idx in 0..expected.len().max(actual.len()) {
+        match (expected.get(idx), actual.get(idx)) {
+            (Some(e), Some(a)) if e == a => println!("      {}", e),
+            (e, a) => {
+                if let Some(e) = e {
+                    println!("    - {}", e);
+                }
+                if let Some(a) = a {
+                    println!("    + {}", a);
+                }
+            }
+        }
+    }
+}
+
+fn check_day(day: &Day, ledger: &Ledger, options: &Options) -> Vec<(u8, Status)> {
+    let answers = read_input(day, false)
+        .map_err(|err| format!("cannot read input: {}", err))
+        .and_then(|input| {
+            day.run_with(&input, &[1, 2], Arithmetic::Native, options)
+                .map_err(|err| err.to_string())
+        });
+
+    match answers {
+        Ok(outcome) => outcome
+            .parts
+            .into_iter()
+            .map(|part| {
+                let actual = part.answer.to_string();
+                let part = part.part;
+                let status = match ledger.get(day.number, part) {
+                    Some(expected) if expected == actual => Status::Pass,
+                    Some(expected) => Status::Fail {
+                        expected: String::from(expected),
+                        actual,
+                    },
+                    None => Status::Missing { actual },
+                };
+                (part, status)
+            })
+            .collect(),
+        Err(err) => vec![(1, Status::Error(err.clone())), (2, Status::Error(err))],
+    }
+}
+
+pub fn verify(mut args: Args) {
+    let record = args.flag("--record");
+    let path = PathBuf::from(
+        args.option("--answers")
+            .unwrap_or_else(|| String::from("answers.toml")),
+    );
+    let options = day_options(&mut args);
+    let days = select_days(args.positional().as_deref().unwrap_or("all"));
+    args.finish();
+
+    let mut ledger = match fs::read_to_string(&path) {
+        Ok(toml) => Ledger::parse(&toml).unwrap_or_else(|err| {
+            eprintln!("error: {}: {}", path.display(), err);
+            process::exit(1)
+        }),
+        Err(_) if record => Ledger::default(),
+        Err(err) => {
+            eprintln!("error: cannot read {}: {}", path.display(), err);
+            process::exit(1)
+        }
+    };
+
+    let mut failed = false;
+    for day in days {
+        println!("Day {}", day.number);
+        for (part, status) in check_day(day, &ledger, &options) {
+            match status {
+                Status::Pass => println!("  Part {}: pass", part),
+                Status::Fail { expected, actual } => {
+                    println!("  Part {}: FAIL", part);
+                    print_diff(&expected, &actual);
+                    failed |= !record;
+                    if record {
+                        ledger.set(day.number, part, actual);
+                    }
+                }
+                Status::Missing { actual } => {
+                    println!("  Part {}: missing", part);
+                    if record {
+                        ledger.set(day.number, part, actual);
+                    }
+                }
+                Status::Error(err) => {
+                    println!("  Part {}: error: {}", part, err);
+                    failed = true;
+                }
+            }
+        }
+    }
+
+    if record {
+        if let Err(err) = fs::write(&path, ledger.to_toml()) {
+            eprintln!("error: cannot write {}: {}", path.display(), err);
+            process::exit(1)
+        }
+        println!("Recorded answers in {}", path.display());
+    }
+    if failed {
+        process::exit(1);
+    }
+}
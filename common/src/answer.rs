@@ -0,0 +1,140 @@
+use std::{fmt::Display, time::Duration};
+
+use crate::{json::Json, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    /// Decimal digits of a number too big for `Number`.
+    Big(String),
+    Text(String),
+}
+
+impl Answer {
+    pub fn to_json(&self) -> Json {
+        match self {
+            Answer::Number(n) => Json::number(n),
+            Answer::Big(digits) => Json::number(digits),
+            Answer::Text(s) => Json::string(s.as_str()),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Big(digits) => write!(f, "{}", digits),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! number_answer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+number_answer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(String::from(s))
+    }
+}
+
+pub struct PartOutcome {
+    pub part: u8,
+    pub answer: Answer,
+    pub time: Duration,
+}
+
+impl Display for PartOutcome {
+    /// Multi-line answers start on their own line.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.answer {
+            Answer::Text(s) if s.contains('\n') => write!(f, "Part {}:\n{}", self.part, s),
+            answer => write!(f, "Part {}: {}", self.part, answer),
+        }
+    }
+}
+
+pub struct Outcome {
+    pub day: u8,
+    pub parse_time: Duration,
+    pub warnings: Vec<String>,
+    pub parts: Vec<PartOutcome>,
+}
+
+impl Outcome {
+    pub fn to_json(&self) -> Json {
+        let parts = self
+            .parts
+            .iter()
+            .map(|part| {
+                Json::Object(vec![
+                    ("part", Json::number(part.part)),
+                    ("answer", part.answer.to_json()),
+                    ("time_ns", Json::number(part.time.as_nanos())),
+                ])
+            })
+            .collect();
+        Json::Object(vec![
+            ("day", Json::number(self.day)),
+            ("parse_time_ns", Json::number(self.parse_time.as_nanos())),
+            (
+                "warnings",
+                Json::Array(self.warnings.iter().map(Json::string).collect()),
+            ),
+            ("parts", Json::Array(parts)),
+        ])
+    }
+}
+
+/// JSON for a day whose input couldn't be parsed.
+pub fn error_json(day: u8, err: &Error) -> Json {
+    Json::Object(vec![("day", Json::number(day)), ("error", err.to_json())])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcome_json_keeps_answer_types() {
+        let outcome = Outcome {
+            day: 13,
+            parse_time: Duration::from_nanos(5),
+            warnings: vec![String::from("odd")],
+            parts: vec![
+                PartOutcome {
+                    part: 1,
+                    answer: Answer::from(17usize),
+                    time: Duration::from_nanos(7),
+                },
+                PartOutcome {
+                    part: 2,
+                    answer: Answer::from("#\n#"),
+                    time: Duration::from_nanos(9),
+                },
+            ],
+        };
+        assert_eq!(
+            outcome.to_json().to_string(),
+            r##"{"day":13,"parse_time_ns":5,"warnings":["odd"],"parts":[{"part":1,"answer":17,"time_ns":7},{"part":2,"answer":"#\n#","time_ns":9}]}"##
+        );
+    }
+}
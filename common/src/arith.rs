@@ -0,0 +1,404 @@
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+use crate::{
+    cli::{usage_error, Args},
+    json::Json,
+    Answer,
+};
+
+/// How solvers do the sums that can outgrow a machine word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arithmetic {
+    /// Plain machine integers, which wrap on overflow in release builds.
+    Native,
+    /// Machine integers that report overflow as an error.
+    Checked,
+    /// Arbitrary precision for counts that are expected to grow without
+    /// bound, like fish and polymer pairs; checked everywhere else.
+    Big,
+}
+
+impl Arithmetic {
+    /// `--arith native|checked|big`, defaulting to native.
+    pub fn from_args(args: &mut Args) -> Self {
+        match args.option("--arith").as_deref() {
+            None | Some("native") => Arithmetic::Native,
+            Some("checked") => Arithmetic::Checked,
+            Some("big") => Arithmetic::Big,
+            Some(other) => usage_error(format!("unknown arithmetic {}", other)),
+        }
+    }
+}
+
+/// An operation whose result didn't fit, and where in the input that was.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    pub lhs: String,
+    pub operator: char,
+    pub rhs: String,
+    pub context: Option<String>,
+}
+
+impl Overflow {
+    pub fn new(lhs: impl Display, operator: char, rhs: impl Display) -> Self {
+        Overflow {
+            lhs: lhs.to_string(),
+            operator,
+            rhs: rhs.to_string(),
+            context: None,
+        }
+    }
+
+    /// Says where the overflow happened, unless something closer to it
+    /// already did.
+    pub fn at(mut self, context: impl Display) -> Self {
+        self.context.get_or_insert_with(|| context.to_string());
+        self
+    }
+
+    pub fn to_json(&self) -> Json {
+        Json::Object(vec![
+            ("lhs", Json::string(self.lhs.as_str())),
+            ("operator", Json::string(self.operator)),
+            ("rhs", Json::string(self.rhs.as_str())),
+            (
+                "context",
+                self.context
+                    .as_ref()
+                    .map(|context| Json::string(context.as_str()))
+                    .unwrap_or(Json::Null),
+            ),
+        ])
+    }
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {} overflows", self.lhs, self.operator, self.rhs)?;
+        if let Some(context) = &self.context {
+            write!(f, " at {}", context)?;
+        }
+        Ok(())
+    }
+}
+
+/// Integer operations that fail with an [`Overflow`] instead of wrapping.
+pub trait Checked: Sized {
+    fn try_add(self, rhs: Self) -> Result<Self, Overflow>;
+    fn try_sub(self, rhs: Self) -> Result<Self, Overflow>;
+    fn try_mul(self, rhs: Self) -> Result<Self, Overflow>;
+}
+
+macro_rules! checked {
+    ($($t:ty),*) => {
+        $(impl Checked for $t {
+            fn try_add(self, rhs: Self) -> Result<Self, Overflow> {
+                self.checked_add(rhs).ok_or_else(|| Overflow::new(self, '+', rhs))
+            }
+
+            fn try_sub(self, rhs: Self) -> Result<Self, Overflow> {
+                self.checked_sub(rhs).ok_or_else(|| Overflow::new(self, '-', rhs))
+            }
+
+            fn try_mul(self, rhs: Self) -> Result<Self, Overflow> {
+                self.checked_mul(rhs).ok_or_else(|| Overflow::new(self, '*', rhs))
+            }
+        })*
+    };
+}
+
+checked!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// A tally that only grows, like the number of fish with a given timer.
+/// Simulations keep stepping through an overflow; it is remembered by the
+/// count and reported once somebody asks.
+pub trait Count: Clone + Ord + Display {
+    fn from_usize(n: usize) -> Self;
+
+    fn increase(&mut self, by: &Self);
+
+    /// `self - smaller`, where `smaller` is no greater than `self`.
+    fn difference(&self, smaller: &Self) -> Self;
+
+    fn overflow(&self) -> Option<&Overflow>;
+
+    fn answer(&self) -> Result<Answer, Overflow> {
+        match self.overflow() {
+            Some(overflow) => Err(overflow.clone()),
+            None => Ok(digits_answer(self.to_string())),
+        }
+    }
+}
+
+fn digits_answer(digits: String) -> Answer {
+    match digits.parse::<i128>() {
+        Ok(n) => Answer::Number(n),
+        Err(_) => Answer::Big(digits),
+    }
+}
+
+impl Count for usize {
+    fn from_usize(n: usize) -> Self {
+        n
+    }
+
+    fn increase(&mut self, by: &Self) {
+        *self += by;
+    }
+
+    fn difference(&self, smaller: &Self) -> Self {
+        self - smaller
+    }
+
+    fn overflow(&self) -> Option<&Overflow> {
+        None
+    }
+}
+
+/// A `u64` count that turns into the first overflow it runs into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckedCount(Result<u64, Overflow>);
+
+impl Count for CheckedCount {
+    fn from_usize(n: usize) -> Self {
+        CheckedCount(Ok(n as u64))
+    }
+
+    fn increase(&mut self, by: &Self) {
+        if let (Ok(a), Ok(b)) = (&self.0, &by.0) {
+            self.0 = a.try_add(*b);
+        } else if let Err(overflow) = &by.0 {
+            self.0 = self.0.clone().and(Err(overflow.clone()));
+        }
+    }
+
+    fn difference(&self, smaller: &Self) -> Self {
+        match (&self.0, &smaller.0) {
+            (Ok(a), Ok(b)) => CheckedCount(a.try_sub(*b)),
+            (Err(overflow), _) | (_, Err(overflow)) => CheckedCount(Err(overflow.clone())),
+        }
+    }
+
+    fn overflow(&self) -> Option<&Overflow> {
+        self.0.as_ref().err()
+    }
+}
+
+/// Overflowed counts sort before all others.
+impl Ord for CheckedCount {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.as_ref().ok().cmp(&other.0.as_ref().ok())
+    }
+}
+
+impl PartialOrd for CheckedCount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for CheckedCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Ok(n) => write!(f, "{}", n),
+            Err(_) => write!(f, "overflow"),
+        }
+    }
+}
+
+/// Unsigned integer of any size, as base 2³² digits with the least
+/// significant first and no trailing zeros.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BigUint {
+    digits: Vec<u32>,
+}
+
+impl BigUint {
+    fn trim(&mut self) {
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+    }
+
+    /// Divides in place and returns the remainder.
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut rem = 0u64;
+        for digit in self.digits.iter_mut().rev() {
+            let value = (rem << 32) | *digit as u64;
+            *digit = (value / divisor as u64) as u32;
+            rem = value % divisor as u64;
+        }
+        self.trim();
+        rem as u32
+    }
+
+    fn mul_add_small(&mut self, factor: u32, add: u32) {
+        let mut carry = add as u64;
+        for digit in self.digits.iter_mut() {
+            let value = *digit as u64 * factor as u64 + carry;
+            *digit = value as u32;
+            carry = value >> 32;
+        }
+        if carry > 0 {
+            self.digits.push(carry as u32);
+        }
+    }
+}
+
+impl Count for BigUint {
+    fn from_usize(n: usize) -> Self {
+        let n = n as u64;
+        let mut big = BigUint {
+            digits: vec![n as u32, (n >> 32) as u32],
+        };
+        big.trim();
+        big
+    }
+
+    fn increase(&mut self, by: &Self) {
+        if self.digits.len() < by.digits.len() {
+            self.digits.resize(by.digits.len(), 0);
+        }
+        let mut carry = 0u64;
+        for (idx, digit) in self.digits.iter_mut().enumerate() {
+            let sum = *digit as u64 + *by.digits.get(idx).unwrap_or(&0) as u64 + carry;
+            *digit = sum as u32;
+            carry = sum >> 32;
+        }
+        if carry > 0 {
+            self.digits.push(carry as u32);
+        }
+    }
+
+    fn difference(&self, smaller: &Self) -> Self {
+        let mut result = self.clone();
+        let mut borrow = 0i64;
+        for (idx, digit) in result.digits.iter_mut().enumerate() {
+            let diff = *digit as i64 - *smaller.digits.get(idx).unwrap_or(&0) as i64 - borrow;
+            borrow = (diff < 0) as i64;
+            *digit = (diff + (borrow << 32)) as u32;
+        }
+        result.trim();
+        result
+    }
+
+    fn overflow(&self) -> Option<&Overflow> {
+        None
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.digits
+            .len()
+            .cmp(&other.digits.len())
+            .then_with(|| self.digits.iter().rev().cmp(other.digits.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const CHUNK: u32 = 1_000_000_000;
+        let mut rest = self.clone();
+        let mut chunks = vec![];
+        loop {
+            chunks.push(rest.div_rem_small(CHUNK));
+            if rest.digits.is_empty() {
+                break;
+            }
+        }
+        let mut chunks = chunks.iter().rev();
+        write!(f, "{}", chunks.next().unwrap())?;
+        for chunk in chunks {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decimal digits, as `Display` writes them.
+impl FromStr for BigUint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("{} is not a decimal number", s));
+        }
+        let mut big = BigUint::default();
+        for digit in s.bytes() {
+            big.mul_add_small(10, (digit - b'0') as u32);
+        }
+        Ok(big)
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(big: BigUint) -> Self {
+        digits_answer(big.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_reports_operands() {
+        assert_eq!(3u64.try_sub(2), Ok(1));
+        let overflow = 3u64.try_sub(5).unwrap_err().at("command 7").at("part 1");
+        assert_eq!(overflow.to_string(), "3 - 5 overflows at command 7");
+        assert_eq!(
+            overflow.to_json().to_string(),
+            r#"{"lhs":"3","operator":"-","rhs":"5","context":"command 7"}"#
+        );
+    }
+
+    #[test]
+    fn checked_count_remembers_overflow() {
+        let mut count = CheckedCount::from_usize(u64::MAX as usize);
+        count.increase(&CheckedCount::from_usize(1));
+        count.increase(&CheckedCount::from_usize(1));
+        assert_eq!(
+            count.overflow().map(|overflow| overflow.rhs.as_str()),
+            Some("1")
+        );
+        assert!(count.answer().is_err());
+    }
+
+    #[test]
+    fn big_counts_past_machine_words() {
+        let mut big = BigUint::from_usize(u64::MAX as usize);
+        big.increase(&BigUint::from_usize(1));
+        assert_eq!(big.to_string(), "18446744073709551616");
+        for _ in 0..4 {
+            big.increase(&big.clone());
+        }
+        assert_eq!(big.to_string(), "295147905179352825856");
+        assert_eq!(
+            big.difference(&BigUint::from_usize(1)).to_string(),
+            "295147905179352825855"
+        );
+        assert!(big > BigUint::from_usize(usize::MAX));
+        assert_eq!(BigUint::from_usize(0).to_string(), "0");
+        assert_eq!(
+            big.answer().unwrap().to_json().to_string(),
+            "295147905179352825856"
+        );
+    }
+
+    #[test]
+    fn big_reads_back_its_digits() {
+        for digits in ["0", "4294967296", "295147905179352825856"] {
+            assert_eq!(digits.parse::<BigUint>().unwrap().to_string(), digits);
+        }
+        assert_eq!("007".parse::<BigUint>(), Ok(BigUint::from_usize(7)));
+        assert!("".parse::<BigUint>().is_err());
+        assert!("12a".parse::<BigUint>().is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use std::{
+    fmt::Display,
+    hint::black_box,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{json::Json, options::Options, ParseError, Solution};
+
+pub struct Stage {
+    pub name: &'static str,
+    samples: Vec<Duration>,
+}
+
+impl Stage {
+    fn measure<T>(name: &'static str, iterations: usize, mut f: impl FnMut() -> T) -> Self {
+        let mut samples = (0..iterations)
+            .map(|_| {
+                let start = Instant::now();
+                black_box(f());
+                start.elapsed()
+            })
+            .collect::<Vec<_>>();
+        samples.sort();
+        Stage { name, samples }
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples[0]
+    }
+
+    pub fn median(&self) -> Duration {
+        self.samples[self.samples.len() / 2]
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples[self.samples.len() - 1]
+    }
+}
+
+pub struct Report {
+    pub day: u8,
+    pub iterations: usize,
+    pub stages: Vec<Stage>,
+}
+
+impl Report {
+    /// Single line of JSON, so results of successive runs can be appended to
+    /// one file and compared.
+    pub fn to_json(&self) -> Json {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let stages = self
+            .stages
+            .iter()
+            .map(|stage| {
+                Json::Object(vec![
+                    ("stage", Json::string(stage.name)),
+                    ("min_ns", Json::number(stage.min().as_nanos())),
+                    ("median_ns", Json::number(stage.median().as_nanos())),
+                    ("max_ns", Json::number(stage.max().as_nanos())),
+                ])
+            })
+            .collect();
+        Json::Object(vec![
+            ("day", Json::number(self.day)),
+            ("timestamp", Json::number(timestamp)),
+            ("iterations", Json::number(self.iterations)),
+            ("stages", Json::Array(stages)),
+        ])
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Day {}, {} iterations", self.day, self.iterations)?;
+        writeln!(
+            f,
+            "  {:<8}{:>14}{:>14}{:>14}",
+            "stage", "min", "median", "max"
+        )?;
+        for stage in self.stages.iter() {
+            writeln!(
+                f,
+                "  {:<8}{:>14}{:>14}{:>14}",
+                stage.name,
+                format!("{:.2?}", stage.min()),
+                format!("{:.2?}", stage.median()),
+                format!("{:.2?}", stage.max())
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Times parsing and both parts separately, `iterations` times each.
+pub fn run<S: Solution>(
+    input: &str,
+    iterations: usize,
+    options: &Options,
+) -> Result<Report, ParseError> {
+    let model = S::parse_with(input, options)?;
+    let stages = vec![
+        Stage::measure("parse", iterations, || S::parse_with(input, options)),
+        Stage::measure("part 1", iterations, || S::part_1(&model)),
+        Stage::measure("part 2", iterations, || S::part_2(&model)),
+    ];
+
+    Ok(Report {
+        day: S::DAY,
+        iterations,
+        stages,
+    })
+}
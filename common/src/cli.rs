@@ -0,0 +1,141 @@
+use std::{
+    env,
+    fmt::Display,
+    fs::read_to_string,
+    io::{self, Read},
+    path::PathBuf,
+    process,
+};
+
+/// Command line arguments that are consumed as they are looked up, so a
+/// binary can pick its own options first and hand the rest over.
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    pub fn from_env() -> Self {
+        Args {
+            args: env::args().skip(1).collect(),
+        }
+    }
+
+    pub fn flag(&mut self, name: &str) -> bool {
+        match self.args.iter().position(|arg| arg == name) {
+            Some(idx) => {
+                self.args.remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Takes `name value` or `name=value`.
+    pub fn option(&mut self, name: &str) -> Option<String> {
+        let prefix = format!("{}=", name);
+        let idx = self
+            .args
+            .iter()
+            .position(|arg| arg == name || arg.starts_with(&prefix))?;
+        let arg = self.args.remove(idx);
+        match arg.strip_prefix(&prefix) {
+            Some(value) => Some(String::from(value)),
+            None if idx < self.args.len() => Some(self.args.remove(idx)),
+            None => usage_error(format!("missing value for {}", name)),
+        }
+    }
+
+    pub fn parsed_option<T: std::str::FromStr>(&mut self, name: &str) -> Option<T> {
+        self.option(name).map(|value| {
+            value
+                .parse()
+                .unwrap_or_else(|_| usage_error(format!("invalid value for {}: {}", name, value)))
+        })
+    }
+
+    /// Takes the next argument that doesn't look like an option. A lone `-`
+    /// counts as positional.
+    pub fn positional(&mut self) -> Option<String> {
+        let idx = self
+            .args
+            .iter()
+            .position(|arg| arg == "-" || !arg.starts_with("--"))?;
+        Some(self.args.remove(idx))
+    }
+
+    /// Fails on anything that nobody asked for.
+    pub fn finish(self) {
+        if let Some(arg) = self.args.first() {
+            usage_error(format!("unexpected argument {}", arg));
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl Format {
+    /// `--format text|json`, defaulting to text.
+    pub fn from_args(args: &mut Args) -> Self {
+        match args.option("--format").as_deref() {
+            None | Some("text") => Format::Text,
+            Some("json") => Format::Json,
+            Some(other) => usage_error(format!("unknown format {}", other)),
+        }
+    }
+}
+
+pub enum Source {
+    Path(PathBuf),
+    Stdin,
+    Example,
+}
+
+impl Source {
+    /// `--example`, `-` for stdin or a path, defaulting to `./input`.
+    pub fn from_args(args: &mut Args) -> Self {
+        if args.flag("--example") {
+            return Source::Example;
+        }
+        match args.positional().as_deref() {
+            Some("-") => Source::Stdin,
+            Some(path) => Source::Path(PathBuf::from(path)),
+            None => Source::Path(PathBuf::from("./input")),
+        }
+    }
+
+    pub fn read(&self, example: &str) -> io::Result<String> {
+        match self {
+            Source::Path(path) => read_to_string(path),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            Source::Example => Ok(String::from(example)),
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Path(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "stdin"),
+            Source::Example => write!(f, "example"),
+        }
+    }
+}
+
+pub fn usage_error(message: impl Display) -> ! {
+    eprintln!("error: {}", message);
+    process::exit(2)
+}
+
+pub fn fail(message: impl Display) -> ! {
+    eprintln!("error: {}", message);
+    process::exit(1)
+}
@@ -0,0 +1,115 @@
+use crate::{run, Answer, Outcome, Solution};
+
+/// splitmix64: tiny, fast and good enough to make reproducible inputs.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..n`; `n` must not be zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Uniform in `low..=high`.
+    pub fn between(&mut self, low: usize, high: usize) -> usize {
+        low + self.below(high - low + 1)
+    }
+
+    pub fn chance(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.below(idx + 1));
+        }
+    }
+}
+
+/// A synthetic puzzle input and whichever answers could be worked out while
+/// building it, without running the solver.
+#[derive(Debug, Clone)]
+pub struct Generated {
+    pub input: String,
+    pub part_1: Option<Answer>,
+    pub part_2: Option<Answer>,
+}
+
+impl Generated {
+    pub fn expected(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Parts where the solver disagrees with the known answer, as
+    /// `(part, expected, actual)`.
+    pub fn mismatches<'a>(&'a self, outcome: &'a Outcome) -> Vec<(u8, &'a Answer, &'a Answer)> {
+        outcome
+            .parts
+            .iter()
+            .filter_map(|part| {
+                let expected = self.expected(part.part)?;
+                (*expected != part.answer).then_some((part.part, expected, &part.answer))
+            })
+            .collect()
+    }
+}
+
+pub trait Generator: Solution {
+    /// What the size counts differs per day: readings, boards, caves...
+    const DEFAULT_SIZE: usize;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated;
+}
+
+/// Generates an input and checks the solver against its known answers.
+pub fn check<S: Generator>(seed: u64, size: usize) -> Result<(), String> {
+    let generated = S::generate(&mut Rng::new(seed), size);
+    let outcome = run::<S>(&generated.input, &[1, 2]).map_err(|err| err.to_string())?;
+    match generated.mismatches(&outcome).first() {
+        None => Ok(()),
+        Some((part, expected, actual)) => Err(format!(
+            "part {}: expected {}, got {}",
+            part, expected, actual
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rng_is_reproducible() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let values = (0..100).map(|_| a.between(3, 7)).collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            (0..100).map(|_| b.between(3, 7)).collect::<Vec<_>>()
+        );
+        assert!(values.iter().all(|v| (3..=7).contains(v)));
+        assert!((3..=7).all(|n| values.contains(&n)));
+    }
+}
@@ -0,0 +1,222 @@
+//! Parsers for the shapes puzzle inputs keep coming in, to be combined into a
+//! day's grammar. Every parser consumes the whole span it is given and points
+//! its errors at the part that didn't fit.
+
+use std::str::FromStr;
+
+use crate::{ParseError, Span};
+
+pub fn number<T: FromStr>(expected: &'static str) -> impl Fn(Span) -> Result<T, ParseError> {
+    move |source: Span| source.parse(expected)
+}
+
+/// Any non-empty text.
+pub fn word(expected: &'static str) -> impl Fn(Span) -> Result<String, ParseError> {
+    move |source: Span| {
+        if source.is_empty() {
+            return Err(source.error(expected));
+        }
+        Ok(String::from(source.as_str()))
+    }
+}
+
+/// Exactly one character.
+pub fn single(expected: &'static str) -> impl Fn(Span) -> Result<char, ParseError> {
+    move |source: Span| {
+        let mut chars = source.chars().map(|(c, _)| c);
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(source.error(expected)),
+        }
+    }
+}
+
+/// One of a fixed set of words, each standing for a value.
+pub fn keyword<T: Copy>(
+    expected: &'static str,
+    options: &'static [(&'static str, T)],
+) -> impl Fn(Span) -> Result<T, ParseError> {
+    move |source: Span| {
+        options
+            .iter()
+            .find(|(word, _)| *word == source.as_str())
+            .map(|&(_, value)| value)
+            .ok_or_else(|| source.error(expected))
+    }
+}
+
+/// One item per character, like `[({(<(())[]>` or `cdfeb`.
+pub fn chars<T>(
+    expected: &'static str,
+    item: impl Fn(char) -> Option<T>,
+) -> impl Fn(Span) -> Result<Vec<T>, ParseError> {
+    move |source: Span| {
+        source
+            .chars()
+            .map(|(c, span)| item(c).ok_or_else(|| span.error(expected)))
+            .collect()
+    }
+}
+
+/// `a <separator> b`, like `x,y`, `a -> b` or `x=n`.
+pub fn pair<A, B>(
+    separator: &'static str,
+    expected: &'static str,
+    a: impl Fn(Span) -> Result<A, ParseError>,
+    b: impl Fn(Span) -> Result<B, ParseError>,
+) -> impl Fn(Span) -> Result<(A, B), ParseError> {
+    move |source: Span| {
+        let (first, second) = source
+            .split_once(separator)
+            .ok_or_else(|| source.error(expected))?;
+        Ok((a(first)?, b(second)?))
+    }
+}
+
+/// `<prefix>inner`, like `fold along x=5`.
+pub fn preceded<T>(
+    prefix: &'static str,
+    expected: &'static str,
+    inner: impl Fn(Span) -> Result<T, ParseError>,
+) -> impl Fn(Span) -> Result<T, ParseError> {
+    move |source: Span| {
+        let rest = source
+            .strip_prefix(prefix)
+            .ok_or_else(|| source.error(expected))?;
+        inner(rest)
+    }
+}
+
+/// Items with a separator between them, like `3,4,3,1,2`.
+pub fn list<T>(
+    separator: &'static str,
+    item: impl Fn(Span) -> Result<T, ParseError>,
+) -> impl Fn(Span) -> Result<Vec<T>, ParseError> {
+    move |source: Span| source.split(separator).map(&item).collect()
+}
+
+/// One item per line.
+pub fn lines<T>(
+    item: impl Fn(Span) -> Result<T, ParseError>,
+) -> impl Fn(Span) -> Result<Vec<T>, ParseError> {
+    move |source: Span| source.lines().map(&item).collect()
+}
+
+/// Blocks separated by blank lines.
+pub fn sections<T>(
+    section: impl Fn(Span) -> Result<T, ParseError>,
+) -> impl Fn(Span) -> Result<Vec<T>, ParseError> {
+    list("\n\n", section)
+}
+
+/// Two blocks separated by a blank line, like dots followed by folds.
+pub fn two_sections<A, B>(
+    expected: &'static str,
+    first: impl Fn(Span) -> Result<A, ParseError>,
+    second: impl Fn(Span) -> Result<B, ParseError>,
+) -> impl Fn(Span) -> Result<(A, B), ParseError> {
+    move |source: Span| {
+        let (a, b) = source
+            .split_once("\n\n")
+            .ok_or_else(|| source.end().error(expected))?;
+        Ok((first(a)?, second(b)?))
+    }
+}
+
+/// Turns what `parser` found into something else.
+pub fn map<T, U>(
+    parser: impl Fn(Span) -> Result<T, ParseError>,
+    f: impl Fn(T) -> U,
+) -> impl Fn(Span) -> Result<U, ParseError> {
+    move |source: Span| parser(source).map(&f)
+}
+
+/// Rejects what `parser` found unless `valid` holds for it.
+pub fn verify<T>(
+    parser: impl Fn(Span) -> Result<T, ParseError>,
+    expected: &'static str,
+    valid: impl Fn(&T) -> bool,
+) -> impl Fn(Span) -> Result<T, ParseError> {
+    move |source: Span| match parser(source)? {
+        value if valid(&value) => Ok(value),
+        _ => Err(source.error(expected)),
+    }
+}
+
+/// Runs a grammar over the whole input.
+pub fn parse<T>(
+    input: &str,
+    grammar: impl Fn(Span) -> Result<T, ParseError>,
+) -> Result<T, ParseError> {
+    grammar(Span::new(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_errors_point_at_the_item() {
+        let line = pair(
+            " -> ",
+            "`x,y -> x,y` line",
+            pair(",", "`x,y` point", number::<u8>("x"), number::<u8>("y")),
+            pair(",", "`x,y` point", number::<u8>("x"), number::<u8>("y")),
+        );
+        assert_eq!(
+            parse("1,2 -> 3,4\n5,6 -> 7,8", lines(&line)).unwrap(),
+            vec![((1, 2), (3, 4)), ((5, 6), (7, 8))]
+        );
+
+        let err = parse("1,2 -> 3,4\n5,6 -> 7;8", lines(&line)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 8));
+        assert_eq!(err.expected, "`x,y` point");
+
+        let err = parse("1,2 -> 3,4\n5,6 => 7,8", lines(&line)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn sections_and_keywords() {
+        let fold = preceded(
+            "fold along ",
+            "fold",
+            pair(
+                "=",
+                "`axis=n`",
+                keyword("axis", &[("x", 0), ("y", 1)]),
+                number::<u32>("position"),
+            ),
+        );
+        let grammar = two_sections("folds", list(",", number::<u8>("n")), lines(fold));
+        assert_eq!(
+            parse("1,2\n\nfold along y=7\nfold along x=5\n", &grammar).unwrap(),
+            (vec![1, 2], vec![(1, 7), (0, 5)])
+        );
+
+        let err = parse("1,2\n\nfold along z=7", &grammar).unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (3, 12, "axis")
+        );
+        let err = parse("1,2\n", &grammar).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn verify_rejects_out_of_range_values() {
+        let timer = verify(number::<u8>("timer"), "timer up to 8", |&t| t <= 8);
+        assert_eq!(parse("3,8", list(",", &timer)).unwrap(), vec![3, 8]);
+        let err = parse("3,9", list(",", &timer)).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (3, "timer up to 8"));
+    }
+
+    #[test]
+    fn chars_point_at_the_character() {
+        let digits = chars("digit", |c| c.to_digit(10));
+        assert_eq!(parse("123", &digits).unwrap(), vec![1, 2, 3]);
+        assert_eq!(parse("", &digits).unwrap(), vec![]);
+        let err = parse("12x", &digits).unwrap_err();
+        assert_eq!((err.column, err.found.as_str()), (3, "x"));
+    }
+}
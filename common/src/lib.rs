@@ -0,0 +1,109 @@
+mod answer;
+pub mod arith;
+pub mod bench;
+pub mod cli;
+pub mod generate;
+pub mod grammar;
+pub mod json;
+pub mod options;
+mod parse;
+pub mod profile;
+pub mod property;
+mod simulation;
+pub mod snapshot;
+mod solution;
+pub mod trace;
+
+use std::{fs::OpenOptions, io::Write};
+
+use arith::Arithmetic;
+use cli::{Args, Format, Source};
+use options::Options;
+
+pub use answer::{error_json, Answer, Outcome, PartOutcome};
+pub use parse::{ParseError, Span};
+pub use simulation::{Cycle, Simulation};
+pub use solution::{run, run_with, Day, Error, Solution};
+
+/// Reads the input, exiting with an error message if that fails.
+pub fn read<S: Solution>(source: &Source) -> String {
+    source
+        .read(S::EXAMPLE)
+        .unwrap_or_else(|err| cli::fail(format!("cannot read {}: {}", source, err)))
+}
+
+/// Reads and parses the input, exiting with an error message if either step
+/// fails.
+pub fn load<S: Solution>(source: &Source) -> S::Model {
+    S::parse(&read::<S>(source)).unwrap_or_else(|err| cli::fail(err))
+}
+
+fn bench<S: Solution>(input: &str, iterations: usize, out: Option<String>, options: &Options) {
+    if iterations == 0 {
+        cli::usage_error("--bench needs at least one iteration");
+    }
+    let report = bench::run::<S>(input, iterations, options).unwrap_or_else(|err| cli::fail(err));
+    print!("{}", report);
+
+    if let Some(path) = out {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "{}", report.to_json()))
+            .unwrap_or_else(|err| cli::fail(format!("cannot write {}: {}", path, err)));
+    }
+}
+
+fn profile<S: Solution>(input: &str, format: Format, options: &Options) {
+    if !profile::installed() {
+        cli::fail("this binary doesn't count allocations");
+    }
+    let report = profile::run::<S>(input, options).unwrap_or_else(|err| cli::fail(err));
+    match format {
+        Format::Text => print!("{}", report),
+        Format::Json => println!("{}", report.to_json()),
+    }
+}
+
+pub fn main<S: Solution>() {
+    main_with::<S>(Args::from_env())
+}
+
+/// `main` for days that took their own options out of the arguments first.
+pub fn main_with<S: Solution>(mut args: Args) {
+    let iterations = args.parsed_option::<usize>("--bench");
+    let bench_out = args.option("--bench-out");
+    let profile = args.flag("--profile");
+    let format = Format::from_args(&mut args);
+    let arithmetic = Arithmetic::from_args(&mut args);
+    trace::from_args(&mut args, format);
+    let options = Options::from_args(&mut args, S::OPTIONS);
+    let source = Source::from_args(&mut args);
+    args.finish();
+
+    let input = read::<S>(&source);
+    if let Some(iterations) = iterations {
+        return bench::<S>(&input, iterations, bench_out, &options);
+    }
+    if profile {
+        return self::profile::<S>(&input, format, &options);
+    }
+
+    match (run_with::<S>(&input, &[1, 2], arithmetic, &options), format) {
+        (Ok(outcome), Format::Text) => {
+            for warning in outcome.warnings.iter() {
+                eprintln!("warning: {}", warning);
+            }
+            for part in outcome.parts.iter() {
+                println!("{}", part);
+            }
+        }
+        (Ok(outcome), Format::Json) => println!("{}", outcome.to_json()),
+        (Err(err), Format::Text) => cli::fail(err),
+        (Err(err), Format::Json) => {
+            println!("{}", error_json(S::DAY, &err));
+            std::process::exit(1)
+        }
+    }
+}
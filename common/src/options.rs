@@ -0,0 +1,100 @@
+//! Flags that only one day takes, like day 1's `--invalid`. A day lists them
+//! in `Solution::OPTIONS` and reads them in `Solution::parse_with`; the day
+//! binaries and `aoc` take them from the command line and hand them down.
+
+use std::{fmt::Display, str::FromStr};
+
+use crate::cli::{usage_error, Args};
+
+/// A day's flag and how to tell whether a value for it is any good.
+pub struct DayOption {
+    pub flag: &'static str,
+    check: fn(&str) -> Result<(), String>,
+}
+
+impl DayOption {
+    /// A flag taking whatever `T` parses from.
+    pub const fn of<T: FromStr>(flag: &'static str) -> Self
+    where
+        T::Err: Display,
+    {
+        DayOption {
+            flag,
+            check: check::<T>,
+        }
+    }
+}
+
+fn check<T: FromStr>(value: &str) -> Result<(), String>
+where
+    T::Err: Display,
+{
+    value
+        .parse::<T>()
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+/// The values given for day options.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    values: Vec<(&'static str, String)>,
+}
+
+impl Options {
+    /// Takes each of `known` from `args`, rejecting values that don't parse.
+    pub fn from_args<'a>(args: &mut Args, known: impl IntoIterator<Item = &'a DayOption>) -> Self {
+        let mut options = Options::default();
+        for option in known {
+            if options.value(option.flag).is_some() {
+                continue;
+            }
+            if let Some(value) = args.option(option.flag) {
+                (option.check)(&value).unwrap_or_else(|err| usage_error(err));
+                options.values.push((option.flag, value));
+            }
+        }
+        options
+    }
+
+    /// Sets `flag` as if it had been given on the command line.
+    pub fn with(mut self, flag: &'static str, value: &str) -> Self {
+        self.values.retain(|(given, _)| *given != flag);
+        self.values.push((flag, String::from(value)));
+        self
+    }
+
+    fn value(&self, flag: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(given, _)| *given == flag)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The value given for `flag`, or the default if there was none.
+    pub fn get<T: FromStr + Default>(&self, flag: &str) -> T {
+        self.value(flag)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_options_take_the_default() {
+        let options = Options::default().with("--size", "3");
+        assert_eq!(options.get::<u8>("--size"), 3);
+        assert_eq!(options.get::<u8>("--depth"), 0);
+        assert_eq!(options.with("--size", "4").get::<u8>("--size"), 4);
+    }
+
+    #[test]
+    fn checks_values() {
+        let size = DayOption::of::<u8>("--size");
+        assert!((size.check)("3").is_ok());
+        assert!((size.check)("300").is_err());
+    }
+}
@@ -0,0 +1,184 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use crate::json::Json;
+
+/// Longest `found` text shown in messages; JSON always carries all of it.
+const MAX_FOUND_LEN: usize = 40;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub found: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn to_json(&self) -> Json {
+        Json::Object(vec![
+            ("line", Json::number(self.line)),
+            ("column", Json::number(self.column)),
+            ("found", Json::string(self.found.as_str())),
+            ("expected", Json::string(self.expected.as_str())),
+        ])
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            write!(f, "end of input")
+        } else if self.found.chars().count() > MAX_FOUND_LEN {
+            let found = self.found.chars().take(MAX_FOUND_LEN).collect::<String>();
+            write!(f, "{:?}...", found)
+        } else {
+            write!(f, "{:?}", self.found)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// A slice of the puzzle input that remembers where it came from, so errors
+/// raised deep inside a parser can still point at a line and column.
+#[derive(Debug, Clone, Copy)]
+pub struct Span<'a> {
+    input: &'a str,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> Span<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Span {
+            input,
+            start: 0,
+            end: input.len(),
+        }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        &self.input[self.start..self.end]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Narrows the span to `part`, which has to be a slice of `self.as_str()`.
+    fn sub(&self, part: &'a str) -> Span<'a> {
+        let start = part.as_ptr() as usize - self.input.as_ptr() as usize;
+        Span {
+            input: self.input,
+            start,
+            end: start + part.len(),
+        }
+    }
+
+    /// Empty span right after the end of this one.
+    pub fn end(&self) -> Span<'a> {
+        Span {
+            input: self.input,
+            start: self.end,
+            end: self.end,
+        }
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        self.sub(self.as_str().trim())
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        let span = *self;
+        self.as_str().lines().map(move |line| span.sub(line))
+    }
+
+    pub fn split(&self, separator: &'a str) -> impl Iterator<Item = Span<'a>> + 'a {
+        let span = *self;
+        self.as_str()
+            .split(separator)
+            .map(move |part| span.sub(part))
+    }
+
+    pub fn split_whitespace(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        let span = *self;
+        self.as_str()
+            .split_whitespace()
+            .map(move |part| span.sub(part))
+    }
+
+    pub fn strip_prefix(&self, prefix: &str) -> Option<Span<'a>> {
+        self.as_str()
+            .strip_prefix(prefix)
+            .map(|rest| self.sub(rest))
+    }
+
+    pub fn split_once(&self, separator: &str) -> Option<(Span<'a>, Span<'a>)> {
+        self.as_str()
+            .split_once(separator)
+            .map(|(a, b)| (self.sub(a), self.sub(b)))
+    }
+
+    pub fn chars(&self) -> impl Iterator<Item = (char, Span<'a>)> + 'a {
+        let span = *self;
+        let text = self.as_str();
+        text.char_indices()
+            .map(move |(idx, c)| (c, span.sub(&text[idx..idx + c.len_utf8()])))
+    }
+
+    pub fn parse<T: FromStr>(&self, expected: &str) -> Result<T, ParseError> {
+        self.as_str().parse().map_err(|_| self.error(expected))
+    }
+
+    pub fn error(&self, expected: &str) -> ParseError {
+        let before = &self.input[..self.start];
+        let line_start = before.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            found: String::from(self.as_str()),
+            expected: String::from(expected),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_points_at_nested_span() {
+        let input = Span::new("1,2\n3,x4\n");
+        let line = input.lines().nth(1).unwrap();
+        let (_, y) = line.split_once(",").unwrap();
+        let err = y.parse::<u8>("number").unwrap_err();
+
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 3);
+        assert_eq!(err.found, "x4");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: expected number, found \"x4\""
+        );
+    }
+
+    #[test]
+    fn error_at_end_of_input() {
+        let input = Span::new("abc");
+        let err = input.end().error("more");
+        assert_eq!((err.line, err.column), (1, 4));
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 4: expected more, found end of input"
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    hint::black_box,
+    sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering::Relaxed},
+};
+
+use crate::{json::Json, options::Options, ParseError, Solution};
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+// Signed, since blocks allocated before counting started can be freed while
+// it's on.
+static CURRENT: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+/// The system allocator, counting what goes through it while `measure` runs.
+/// Binaries that want `--profile` to work install it with
+/// `#[global_allocator] static ALLOCATOR: Counting = Counting;`. The rest of
+/// the time it only checks a flag, so it doesn't slow down ordinary runs.
+pub struct Counting;
+
+fn allocated(size: usize) {
+    if !COUNTING.load(Relaxed) {
+        return;
+    }
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    BYTES.fetch_add(size, Relaxed);
+    let current = CURRENT.fetch_add(size as isize, Relaxed) + size as isize;
+    PEAK.fetch_max(current, Relaxed);
+}
+
+fn freed(size: usize) {
+    if COUNTING.load(Relaxed) {
+        CURRENT.fetch_sub(size as isize, Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        freed(layout.size());
+    }
+
+    /// Counts as freeing the old block and allocating the new one, which is
+    /// what growing a `Vec` costs when it has to move.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Whether allocations are being counted, i.e. the running binary installed
+/// `Counting`.
+pub fn installed() -> bool {
+    let (_, usage) = measure(|| drop(black_box(Box::new(0u8))));
+    usage.allocations > 0
+}
+
+/// What a stage allocated. `peak` is the most it held at once on top of what
+/// was already allocated when it started.
+#[derive(Debug, Clone, Copy)]
+pub struct Usage {
+    pub allocations: usize,
+    pub bytes: usize,
+    pub peak: usize,
+}
+
+/// Runs `f`, counting what it allocates. Counts are global, so anything
+/// running on other threads at the same time is counted too.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let counting = COUNTING.swap(true, Relaxed);
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let bytes = BYTES.load(Relaxed);
+    let current = CURRENT.load(Relaxed);
+    PEAK.store(current, Relaxed);
+
+    let result = black_box(f());
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+        bytes: BYTES.load(Relaxed) - bytes,
+        peak: (PEAK.load(Relaxed) - current).max(0) as usize,
+    };
+    COUNTING.store(counting, Relaxed);
+    (result, usage)
+}
+
+fn size(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+pub struct Report {
+    pub day: u8,
+    pub stages: Vec<(&'static str, Usage)>,
+}
+
+impl Report {
+    pub fn to_json(&self) -> Json {
+        let stages = self
+            .stages
+            .iter()
+            .map(|(name, usage)| {
+                Json::Object(vec![
+                    ("stage", Json::string(*name)),
+                    ("allocations", Json::number(usage.allocations)),
+                    ("bytes", Json::number(usage.bytes)),
+                    ("peak_bytes", Json::number(usage.peak)),
+                ])
+            })
+            .collect();
+        Json::Object(vec![
+            ("day", Json::number(self.day)),
+            ("stages", Json::Array(stages)),
+        ])
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Day {}", self.day)?;
+        writeln!(
+            f,
+            "  {:<8}{:>14}{:>14}{:>14}",
+            "stage", "allocations", "allocated", "peak"
+        )?;
+        for (name, usage) in self.stages.iter() {
+            writeln!(
+                f,
+                "  {:<8}{:>14}{:>14}{:>14}",
+                name,
+                usage.allocations,
+                size(usage.bytes),
+                size(usage.peak)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Counts what parsing and each part allocate, once each.
+pub fn run<S: Solution>(input: &str, options: &Options) -> Result<Report, ParseError> {
+    let (model, parse) = measure(|| S::parse_with(input, options));
+    let model = model?;
+    let (_, part_1) = measure(|| S::part_1(&model));
+    let (_, part_2) = measure(|| S::part_2(&model));
+
+    Ok(Report {
+        day: S::DAY,
+        stages: vec![("parse", parse), ("part 1", part_1), ("part 2", part_2)],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    #[test]
+    fn counts_allocations() {
+        assert!(installed());
+        let (_, usage) = measure(|| {
+            let small = vec![0u8; 100];
+            let large = vec![0u64; 1000];
+            (small, large)
+        });
+        assert!(usage.allocations >= 2);
+        assert!(usage.bytes >= 8100);
+    }
+
+    #[test]
+    fn sizes_are_readable() {
+        assert_eq!(size(512), "512 B");
+        assert_eq!(size(1536), "1.5 KiB");
+        assert_eq!(size(3 << 20), "3.0 MiB");
+    }
+}
@@ -0,0 +1,36 @@
+use std::{
+    env,
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::generate::Rng;
+
+/// Runs `property` against `cases` random inputs, one seed each. A failing
+/// case reports its seed; `PROPERTY_SEED` shifts the seeds to explore new
+/// inputs and `PROPERTY_CASES` overrides the number of cases.
+pub fn check(cases: usize, property: impl Fn(&mut Rng)) {
+    let from_env = |name| env::var(name).ok().and_then(|v| v.parse().ok());
+    let first_seed = from_env("PROPERTY_SEED").unwrap_or(0);
+    let cases = from_env("PROPERTY_CASES").map_or(cases, |cases| cases as usize);
+
+    for seed in first_seed..first_seed + cases as u64 {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| property(&mut Rng::new(seed))));
+        if let Err(err) = result {
+            eprintln!("property failed with seed {}", seed);
+            panic::resume_unwind(err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_failing_seed() {
+        let result = panic::catch_unwind(|| {
+            check(100, |rng| assert!(rng.below(10) != 3));
+        });
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Where a simulation starts repeating itself: the state after
+/// `start + len` steps is the same as the one after `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as `step`.
+    pub fn equivalent(&self, step: usize) -> usize {
+        if step < self.start {
+            step
+        } else {
+            self.start + (step - self.start) % self.len
+        }
+    }
+}
+
+/// Something that advances one step at a time. Step counts are relative to
+/// the state the simulation was in when a method was called.
+pub trait Simulation {
+    fn step(&mut self);
+
+    fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    /// Steps until `done` holds and returns how many steps that took.
+    fn run_until(&mut self, mut done: impl FnMut(&Self) -> bool) -> usize
+    where
+        Self: Sized,
+    {
+        let mut steps = 0;
+        loop {
+            self.step();
+            steps += 1;
+            if done(self) {
+                return steps;
+            }
+        }
+    }
+
+    /// Runs `steps` steps, recording `capture` of the state before the first
+    /// one and after each of them.
+    fn history<T>(&mut self, steps: usize, mut capture: impl FnMut(&Self) -> T) -> Vec<T>
+    where
+        Self: Sized,
+    {
+        let mut history = Vec::with_capacity(steps + 1);
+        history.push(capture(self));
+        for _ in 0..steps {
+            self.step();
+            history.push(capture(self));
+        }
+        history
+    }
+
+    /// Steps until a state repeats, comparing states by `key`. Gives up after
+    /// `limit` steps. On success the simulation is left at the repeated state,
+    /// `start + len` steps in.
+    fn find_cycle<K: Hash + Eq>(&mut self, limit: usize, key: impl Fn(&Self) -> K) -> Option<Cycle>
+    where
+        Self: Sized,
+    {
+        let mut seen = HashMap::new();
+        seen.insert(key(self), 0);
+        for step in 1..=limit {
+            self.step();
+            if let Some(start) = seen.insert(key(self), step) {
+                return Some(Cycle {
+                    start,
+                    len: step - start,
+                });
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up to 10 and then loops through 5..10.
+    struct Counter(u32);
+
+    impl Simulation for Counter {
+        fn step(&mut self) {
+            self.0 = if self.0 == 9 { 5 } else { self.0 + 1 };
+        }
+    }
+
+    #[test]
+    fn run_and_run_until() {
+        let mut counter = Counter(0);
+        counter.run(3);
+        assert_eq!(counter.0, 3);
+        assert_eq!(counter.run_until(|c| c.0 == 8), 5);
+    }
+
+    #[test]
+    fn history_includes_initial_state() {
+        let mut counter = Counter(7);
+        assert_eq!(counter.history(4, |c| c.0), vec![7, 8, 9, 5, 6]);
+    }
+
+    #[test]
+    fn finds_cycle() {
+        let mut counter = Counter(0);
+        let cycle = counter.find_cycle(100, |c| c.0).unwrap();
+        assert_eq!(cycle, Cycle { start: 5, len: 5 });
+        assert_eq!(counter.0, 5);
+        assert_eq!(cycle.equivalent(3), 3);
+        assert_eq!(cycle.equivalent(1_000_003), 8);
+
+        assert_eq!(Counter(0).find_cycle(7, |c| c.0), None);
+    }
+}
@@ -0,0 +1,193 @@
+use std::{fs, path::PathBuf};
+
+use crate::{
+    cli::{self, Args, Format, Source},
+    trace, Answer, ParseError, Simulation, Solution, Span,
+};
+
+/// A simulation that can be written out as text and read back in, so a long
+/// run can be picked up where it stopped.
+pub trait Snapshot: Simulation + Sized {
+    /// Writes the state as lines of text that `restore` accepts, always in
+    /// the same order for the same state.
+    fn save(&self, out: &mut String);
+
+    fn restore(input: Span) -> Result<Self, ParseError>;
+}
+
+/// Days built around a simulation worth checkpointing.
+pub trait Simulate: Solution {
+    type State: Snapshot;
+
+    fn start(model: &Self::Model) -> Self::State;
+
+    /// What to report about the state once the run is over.
+    fn summary(state: &Self::State) -> Answer;
+}
+
+/// The state after `step` steps, with a header naming the day and step.
+pub fn write<S: Simulate>(step: usize, state: &S::State) -> String {
+    let mut out = format!("day {}\nstep {}\n", S::DAY, step);
+    state.save(&mut out);
+    out
+}
+
+/// Splits a `<name> <n>` line off the front of `input`.
+fn header<'a>(input: Span<'a>, name: &str) -> Result<(Span<'a>, Span<'a>), ParseError> {
+    let (line, rest) = input.split_once("\n").unwrap_or((input, input.end()));
+    let value = line
+        .strip_prefix(name)
+        .and_then(|rest| rest.strip_prefix(" "))
+        .ok_or_else(|| line.error(&format!("`{} <n>` line", name)))?;
+    Ok((value, rest))
+}
+
+pub fn read<S: Simulate>(input: &str) -> Result<(usize, S::State), ParseError> {
+    let (day, rest) = header(Span::new(input), "day")?;
+    if day.parse::<u8>("day number")? != S::DAY {
+        return Err(day.error(&format!("snapshot of day {}", S::DAY)));
+    }
+    let (step, state) = header(rest, "step")?;
+    Ok((step.parse("step number")?, S::State::restore(state)?))
+}
+
+/// Writes to a temporary file first so an interrupted save never leaves a
+/// half written snapshot behind.
+fn save<S: Simulate>(path: &PathBuf, step: usize, state: &S::State) {
+    let temporary = path.with_extension("tmp");
+    fs::write(&temporary, write::<S>(step, state))
+        .and_then(|_| fs::rename(&temporary, path))
+        .unwrap_or_else(|err| cli::fail(format!("cannot write {}: {}", path.display(), err)));
+}
+
+/// Entry point for days with a simulation: `--steps N` runs it for N steps in
+/// total, starting from the input or from `--resume <file>`, and
+/// `--save-every N` checkpoints it to `--snapshot <file>` (by default the
+/// resumed file or `day<N>.snapshot`). Anything else runs the solution as
+/// usual.
+pub fn main<S: Simulate>() {
+    let mut args = Args::from_env();
+    let steps = args.parsed_option::<usize>("--steps");
+    let save_every = args.parsed_option::<usize>("--save-every");
+    let resume = args.option("--resume").map(PathBuf::from);
+    let snapshot = args.option("--snapshot").map(PathBuf::from);
+    let steps = match steps {
+        Some(steps) => steps,
+        None if save_every.is_none() && resume.is_none() && snapshot.is_none() => {
+            return crate::main_with::<S>(args)
+        }
+        None => cli::usage_error("--save-every, --resume and --snapshot need --steps"),
+    };
+    trace::from_args(&mut args, Format::Text);
+    if save_every == Some(0) {
+        cli::usage_error("--save-every needs at least one step");
+    }
+
+    let (mut step, mut state) = match &resume {
+        Some(path) => {
+            args.finish();
+            let text = fs::read_to_string(path).unwrap_or_else(|err| {
+                cli::fail(format!("cannot read {}: {}", path.display(), err))
+            });
+            read::<S>(&text).unwrap_or_else(|err| cli::fail(format!("{}: {}", path.display(), err)))
+        }
+        None => {
+            let source = Source::from_args(&mut args);
+            args.finish();
+            (0, S::start(&crate::load::<S>(&source)))
+        }
+    };
+    if step > steps {
+        cli::fail(format!("snapshot is already at step {}", step));
+    }
+
+    let path = snapshot
+        .or(resume)
+        .unwrap_or_else(|| PathBuf::from(format!("day{}.snapshot", S::DAY)));
+    while step < steps {
+        state.step();
+        step += 1;
+        match save_every {
+            Some(every) if step % every == 0 || step == steps => save::<S>(&path, step, &state),
+            _ => {}
+        }
+    }
+    println!("Step {}: {}", step, S::summary(&state));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Counter(usize);
+
+    impl Simulation for Counter {
+        fn step(&mut self) {
+            self.0 += 1;
+        }
+    }
+
+    impl Snapshot for Counter {
+        fn save(&self, out: &mut String) {
+            out.push_str(&format!("{}\n", self.0));
+        }
+
+        fn restore(input: Span) -> Result<Self, ParseError> {
+            input.trim().parse("count").map(Counter)
+        }
+    }
+
+    struct Day0;
+
+    impl Solution for Day0 {
+        const DAY: u8 = 0;
+        const EXAMPLE: &'static str = "";
+
+        type Model = ();
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse(_: &str) -> Result<Self::Model, ParseError> {
+            Ok(())
+        }
+
+        fn part_1(_: &Self::Model) -> Self::Part1 {
+            0
+        }
+
+        fn part_2(_: &Self::Model) -> Self::Part2 {
+            0
+        }
+    }
+
+    impl Simulate for Day0 {
+        type State = Counter;
+
+        fn start(_: &Self::Model) -> Self::State {
+            Counter(0)
+        }
+
+        fn summary(state: &Self::State) -> Answer {
+            state.0.into()
+        }
+    }
+
+    #[test]
+    fn round_trips_with_step() {
+        let text = write::<Day0>(12, &Counter(7));
+        assert_eq!(text, "day 0\nstep 12\n7\n");
+        let (step, state) = read::<Day0>(&text).unwrap();
+        assert_eq!((step, state.0), (12, 7));
+    }
+
+    #[test]
+    fn rejects_other_days() {
+        let err = read::<Day0>("day 6\nstep 12\n7\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 5));
+        assert_eq!(err.expected, "snapshot of day 0");
+
+        let err = read::<Day0>("day 0\n12\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+}
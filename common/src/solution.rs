@@ -0,0 +1,174 @@
+use std::{error, fmt::Display, time::Instant};
+
+use crate::{
+    arith::{Arithmetic, Overflow},
+    generate::{Generated, Generator, Rng},
+    json::Json,
+    options::{DayOption, Options},
+    profile, Answer, Outcome, ParseError, PartOutcome,
+};
+
+pub trait Solution {
+    const DAY: u8;
+    /// Sample input from the puzzle statement.
+    const EXAMPLE: &'static str;
+
+    type Model;
+    type Part1: Into<Answer>;
+    type Part2: Into<Answer>;
+
+    /// Flags this day takes on top of the ones every day does.
+    const OPTIONS: &'static [DayOption] = &[];
+
+    fn parse(input: &str) -> Result<Self::Model, ParseError>;
+
+    /// Parses with the values given for `OPTIONS`. Days with options
+    /// override this and have `parse` use the defaults.
+    fn parse_with(input: &str, _options: &Options) -> Result<Self::Model, ParseError> {
+        Self::parse(input)
+    }
+    fn part_1(model: &Self::Model) -> Self::Part1;
+    fn part_2(model: &Self::Model) -> Self::Part2;
+
+    /// Problems with the input that didn't stop it from being parsed.
+    fn warnings(_model: &Self::Model) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Solves `part` with checked or arbitrary precision arithmetic. Only
+    /// days with sums that can overflow need to override this.
+    fn solve(model: &Self::Model, part: u8, _arithmetic: Arithmetic) -> Result<Answer, Overflow> {
+        Ok(match part {
+            1 => Self::part_1(model).into(),
+            _ => Self::part_2(model).into(),
+        })
+    }
+}
+
+/// Why a day couldn't be solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    Overflow { part: u8, overflow: Overflow },
+}
+
+impl Error {
+    /// The parse error itself, or `{"part":..,"overflow":{..}}`.
+    pub fn to_json(&self) -> Json {
+        match self {
+            Error::Parse(err) => err.to_json(),
+            Error::Overflow { part, overflow } => Json::Object(vec![
+                ("part", Json::number(part)),
+                ("overflow", overflow.to_json()),
+            ]),
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "{}", err),
+            Error::Overflow { part, overflow } => write!(f, "part {}: {}", part, overflow),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// Type-erased handle to a [`Solution`], so days can be stored side by side.
+pub struct Day {
+    pub number: u8,
+    pub example: &'static str,
+    pub default_size: usize,
+    pub options: &'static [DayOption],
+    generate: fn(&mut Rng, usize) -> Generated,
+    run: fn(&str, &[u8], Arithmetic, &Options) -> Result<Outcome, Error>,
+    profile: fn(&str, &Options) -> Result<profile::Report, ParseError>,
+}
+
+impl Day {
+    pub const fn of<S: Generator>() -> Self {
+        Day {
+            number: S::DAY,
+            example: S::EXAMPLE,
+            default_size: S::DEFAULT_SIZE,
+            options: S::OPTIONS,
+            generate: S::generate,
+            run: run_with::<S>,
+            profile: profile::run::<S>,
+        }
+    }
+
+    pub fn generate(&self, rng: &mut Rng, size: usize) -> Generated {
+        (self.generate)(rng, size)
+    }
+
+    pub fn run(&self, input: &str, parts: &[u8]) -> Result<Outcome, Error> {
+        (self.run)(input, parts, Arithmetic::Native, &Options::default())
+    }
+
+    pub fn run_with(
+        &self,
+        input: &str,
+        parts: &[u8],
+        arithmetic: Arithmetic,
+        options: &Options,
+    ) -> Result<Outcome, Error> {
+        (self.run)(input, parts, arithmetic, options)
+    }
+
+    /// Counts what parsing and both parts allocate; only meaningful in
+    /// binaries that installed `profile::Counting`.
+    pub fn profile(&self, input: &str, options: &Options) -> Result<profile::Report, ParseError> {
+        (self.profile)(input, options)
+    }
+}
+
+/// Parses the input and solves the requested parts, timing each step.
+pub fn run<S: Solution>(input: &str, parts: &[u8]) -> Result<Outcome, Error> {
+    run_with::<S>(input, parts, Arithmetic::Native, &Options::default())
+}
+
+pub fn run_with<S: Solution>(
+    input: &str,
+    parts: &[u8],
+    arithmetic: Arithmetic,
+    options: &Options,
+) -> Result<Outcome, Error> {
+    let start = Instant::now();
+    let model = S::parse_with(input, options)?;
+    let parse_time = start.elapsed();
+
+    let parts = parts
+        .iter()
+        .filter(|&&part| part == 1 || part == 2)
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match (arithmetic, part) {
+                (Arithmetic::Native, 1) => S::part_1(&model).into(),
+                (Arithmetic::Native, _) => S::part_2(&model).into(),
+                _ => S::solve(&model, part, arithmetic)
+                    .map_err(|overflow| Error::Overflow { part, overflow })?,
+            };
+            Ok(PartOutcome {
+                part,
+                answer,
+                time: start.elapsed(),
+            })
+        })
+        .collect::<Result<_, Error>>()?;
+
+    Ok(Outcome {
+        day: S::DAY,
+        parse_time,
+        warnings: S::warnings(&model),
+        parts,
+    })
+}
@@ -0,0 +1,225 @@
+use std::{fmt, str::FromStr, sync::OnceLock};
+
+use crate::{
+    cli::{usage_error, Args, Format},
+    json::Json,
+};
+
+/// How much detail an event carries, from rare and important to chatty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    /// One event per decision a solver makes, like a board winning.
+    Info,
+    /// One event per item a solver looks at, like a path it found.
+    Debug,
+    Trace,
+}
+
+impl Level {
+    fn name(&self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Level::Error,
+            Level::Warn,
+            Level::Info,
+            Level::Debug,
+            Level::Trace,
+        ]
+        .into_iter()
+        .find(|level| level.name() == s)
+        .ok_or(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Directive {
+    day: Option<u8>,
+    component: Option<String>,
+    level: Level,
+}
+
+impl Directive {
+    fn matches(&self, day: Option<u8>, component: &str) -> bool {
+        self.day.is_none_or(|d| Some(d) == day)
+            && self.component.as_deref().is_none_or(|c| c == component)
+    }
+
+    fn specificity(&self) -> usize {
+        self.day.is_some() as usize + self.component.is_some() as usize
+    }
+}
+
+/// Which events to show: comma separated `[day<N>][:component][=level]`
+/// directives, or a bare level for everything. The most specific directive
+/// that matches an event decides; `info` is assumed when no level is given.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Filter {
+    directives: Vec<Directive>,
+}
+
+impl Filter {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let directives = spec
+            .split(',')
+            .map(|directive| {
+                let invalid = || format!("invalid trace directive `{}`", directive);
+                let (target, level) = match directive.split_once('=') {
+                    Some((target, level)) => (target, level.parse().map_err(|_| invalid())?),
+                    None => match directive.parse() {
+                        Ok(level) => ("", level),
+                        Err(_) => (directive, Level::Info),
+                    },
+                };
+                let (day, component) = match target.split_once(':') {
+                    Some((day, component)) => (Some(day), Some(component)),
+                    None if target.starts_with("day") => (Some(target), None),
+                    None => (None, Some(target)),
+                };
+                let day = match day.filter(|day| !day.is_empty()) {
+                    Some(day) => Some(
+                        day.strip_prefix("day")
+                            .and_then(|n| n.parse().ok())
+                            .ok_or_else(invalid)?,
+                    ),
+                    None => None,
+                };
+                let component = component
+                    .filter(|component| !component.is_empty())
+                    .map(String::from);
+                Ok(Directive {
+                    day,
+                    component,
+                    level,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Filter { directives })
+    }
+
+    fn level(&self, day: Option<u8>, component: &str) -> Option<Level> {
+        self.directives
+            .iter()
+            .filter(|directive| directive.matches(day, component))
+            .max_by_key(|directive| directive.specificity())
+            .map(|directive| directive.level)
+    }
+}
+
+struct Tracer {
+    filter: Filter,
+    format: Format,
+}
+
+static TRACER: OnceLock<Tracer> = OnceLock::new();
+
+/// `--trace <filter>`; events go to stderr, as JSON lines in JSON format.
+pub fn from_args(args: &mut Args, format: Format) {
+    if let Some(spec) = args.option("--trace") {
+        let filter = Filter::parse(&spec).unwrap_or_else(|err| usage_error(err));
+        let _ = TRACER.set(Tracer { filter, format });
+    }
+}
+
+/// The day a module belongs to, going by its crate name.
+fn day_of(module: &str) -> Option<u8> {
+    module.split("::").next()?.strip_prefix("day")?.parse().ok()
+}
+
+pub fn enabled(level: Level, module: &str, component: &str) -> bool {
+    TRACER
+        .get()
+        .and_then(|tracer| tracer.filter.level(day_of(module), component))
+        .is_some_and(|max| level <= max)
+}
+
+pub fn emit(level: Level, module: &str, component: &str, message: fmt::Arguments) {
+    let format = TRACER.get().map_or(Format::Text, |tracer| tracer.format);
+    let day = day_of(module);
+    match format {
+        Format::Text => match day {
+            Some(day) => eprintln!("day{} {} {}: {}", day, component, level.name(), message),
+            None => eprintln!("{} {}: {}", component, level.name(), message),
+        },
+        Format::Json => eprintln!(
+            "{}",
+            Json::Object(vec![
+                ("day", day.map_or(Json::Null, Json::number)),
+                ("component", Json::string(component)),
+                ("level", Json::string(level.name())),
+                ("message", Json::string(message.to_string())),
+            ])
+        ),
+    }
+}
+
+/// Whether `--trace` asked for events at this level from this component,
+/// for when gathering what to report costs something.
+#[macro_export]
+macro_rules! trace_enabled {
+    ($level:ident, $component:expr) => {
+        $crate::trace::enabled($crate::trace::Level::$level, module_path!(), $component)
+    };
+}
+
+/// Reports an event from a solver, like
+/// `trace!(Info, "bingo", "board {} won on draw {}", board, draw)`. The
+/// message is only formatted when `--trace` asked for it.
+#[macro_export]
+macro_rules! trace {
+    ($level:ident, $component:expr, $($message:tt)+) => {
+        if $crate::trace_enabled!($level, $component) {
+            $crate::trace::emit(
+                $crate::trace::Level::$level,
+                module_path!(),
+                $component,
+                format_args!($($message)+),
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn most_specific_directive_wins() {
+        let filter = Filter::parse("warn,day4,day13:fold=trace,paths=debug").unwrap();
+        assert_eq!(filter.level(Some(1), "sonar"), Some(Level::Warn));
+        assert_eq!(filter.level(Some(4), "bingo"), Some(Level::Info));
+        assert_eq!(filter.level(Some(13), "fold"), Some(Level::Trace));
+        assert_eq!(filter.level(Some(13), "paper"), Some(Level::Warn));
+        assert_eq!(filter.level(Some(12), "paths"), Some(Level::Debug));
+
+        let filter = Filter::parse("day4").unwrap();
+        assert_eq!(filter.level(Some(5), "vents"), None);
+    }
+
+    #[test]
+    fn rejects_bad_directives() {
+        assert!(Filter::parse("day4=loud").is_err());
+        assert!(Filter::parse("dayx:fold").is_err());
+    }
+
+    #[test]
+    fn day_comes_from_crate_name() {
+        assert_eq!(day_of("day13"), Some(13));
+        assert_eq!(day_of("day4::bingo"), Some(4));
+        assert_eq!(day_of("common::trace"), None);
+    }
+}
@@ -0,0 +1,169 @@
+use std::io::{self, BufRead};
+
+use common::{
+    cli::{self, Args, Format, Source},
+    json::Json,
+    options::Options,
+    Solution,
+};
+
+use crate::{
+    count_increases,
+    report::Report,
+    validate::{Gap, Policy, Problem, Readings},
+    Day1, WindowCounter,
+};
+
+/// `--window k[,k...]` counts increases for other window sizes than the two
+/// parts do, `--stream` counts them while reading stdin and `--report`
+/// describes the series; anything else goes to the usual day binary.
+/// `--invalid skip|interpolate|fail` applies to all of them.
+pub fn main() {
+    let mut args = Args::from_env();
+    let stream = args.flag("--stream");
+    let report = args.flag("--report");
+    if stream && report {
+        cli::usage_error("--stream and --report don't go together");
+    }
+    let windows = match args.option("--window") {
+        Some(windows) => parse_windows(&windows),
+        None if stream => vec![1, 3],
+        None if report => vec![1],
+        None => return common::main_with::<Day1>(args),
+    };
+    let policy = Options::from_args(&mut args, Day1::OPTIONS).get::<Policy>("--invalid");
+    let format = Format::from_args(&mut args);
+    if stream {
+        args.finish();
+        return self::stream(&windows, policy, format)
+            .unwrap_or_else(|err| cli::fail(format!("stdin: {}", err)));
+    }
+    let source = Source::from_args(&mut args);
+    args.finish();
+
+    let input = common::read::<Day1>(&source);
+    let readings = Readings::validate(&input, policy).unwrap_or_else(|err| cli::fail(err));
+    for warning in Day1::warnings(&readings) {
+        eprintln!("warning: {}", warning);
+    }
+    let depths = readings.depths;
+    if report {
+        let reports = windows.iter().map(|&window| Report::new(&depths, window));
+        match format {
+            Format::Text => reports.for_each(|report| print!("{}", report)),
+            Format::Json => {
+                let reports = reports.map(|report| report.to_json()).collect();
+                println!("{}", Json::Array(reports));
+            }
+        }
+        return;
+    }
+    let counts = windows
+        .iter()
+        .map(|&window| (window, count_increases(&depths, window)))
+        .collect::<Vec<_>>();
+    match format {
+        Format::Text => {
+            for (window, increases) in counts {
+                println!("Window {}: {}", window, increases);
+            }
+        }
+        Format::Json => {
+            println!(
+                "{}",
+                Json::Object(vec![
+                    ("day", Json::number(Day1::DAY)),
+                    ("windows", counts_json(counts)),
+                ])
+            );
+        }
+    }
+}
+
+fn counts_json(counts: impl IntoIterator<Item = (usize, u32)>) -> Json {
+    let windows = counts
+        .into_iter()
+        .map(|(window, increases)| {
+            Json::Object(vec![
+                ("window", Json::number(window)),
+                ("increases", Json::number(increases)),
+            ])
+        })
+        .collect();
+    Json::Array(windows)
+}
+
+/// Reads one depth per line from stdin and prints the counts after each,
+/// keeping only the last few readings.
+fn stream(windows: &[usize], policy: Policy, format: Format) -> io::Result<()> {
+    let mut counters = windows
+        .iter()
+        .map(|&window| WindowCounter::new(window))
+        .collect::<Vec<_>>();
+    let mut gap = Gap::default();
+    let stdin = io::stdin();
+    let mut stdin = stdin.lock();
+    let mut line = String::new();
+    let mut number = 0;
+    while stdin.read_line(&mut line)? > 0 {
+        number += 1;
+        let checked = Problem::check(number, line.trim_end_matches(['\n', '\r']));
+        line.clear();
+        match (checked, policy) {
+            (Ok(depth), Policy::Interpolate) => {
+                for (number, depth) in gap.close(number, depth) {
+                    push(&mut counters, number, depth, format);
+                }
+            }
+            (Ok(depth), _) => push(&mut counters, number, depth, format),
+            (Err((_, err)), Policy::Fail) => cli::fail(err),
+            (Err((problem, _)), _) => {
+                eprintln!("warning: {} {}", problem, policy);
+                gap.open(number);
+            }
+        }
+    }
+    if policy == Policy::Interpolate {
+        for (number, depth) in gap.finish() {
+            push(&mut counters, number, depth, format);
+        }
+    }
+    Ok(())
+}
+
+/// Counts the reading from line `number` and prints where the counts are at.
+fn push(counters: &mut [WindowCounter], reading: usize, depth: u32, format: Format) {
+    for counter in counters.iter_mut() {
+        counter.push(depth);
+    }
+    match format {
+        Format::Text => {
+            let counts = counters
+                .iter()
+                .map(|counter| format!("window {}: {}", counter.window(), counter.increases))
+                .collect::<Vec<_>>();
+            println!("{} ({}): {}", reading, depth, counts.join(", "));
+        }
+        Format::Json => println!(
+            "{}",
+            Json::Object(vec![
+                ("reading", Json::number(reading)),
+                ("depth", Json::number(depth)),
+                (
+                    "windows",
+                    counts_json(counters.iter().map(|c| (c.window(), c.increases))),
+                ),
+            ])
+        ),
+    }
+}
+
+fn parse_windows(windows: &str) -> Vec<usize> {
+    windows
+        .split(',')
+        .map(|window| match window.parse() {
+            Ok(window) if window > 0 => window,
+            _ => cli::usage_error(format!("invalid window size {}", window)),
+        })
+        .collect()
+}
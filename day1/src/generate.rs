@@ -0,0 +1,46 @@
+use common::generate::{Generated, Generator, Rng};
+
+use crate::Day1;
+
+impl Generator for Day1 {
+    /// Number of depth readings.
+    const DEFAULT_SIZE: usize = 2000;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut depths = Vec::with_capacity(size);
+        let mut depth = rng.between(100, 200) as u32;
+        for _ in 0..size {
+            depths.push(depth);
+            // mostly sinking, sometimes rising, never above the surface
+            depth = (depth + rng.between(0, 40) as u32).saturating_sub(15);
+        }
+
+        // two overlapping windows share all but their outermost readings
+        let increases = |gap: usize| {
+            depths
+                .iter()
+                .zip(depths.iter().skip(gap))
+                .filter(|(a, b)| b > a)
+                .count()
+        };
+
+        Generated {
+            input: depths.iter().map(|depth| format!("{}\n", depth)).collect(),
+            part_1: Some(increases(1).into()),
+            part_2: Some(increases(3).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generate::check;
+
+    #[test]
+    fn generated_answers_match() {
+        for seed in 0..10 {
+            assert_eq!(check::<Day1>(seed, 200), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use common::{
+    options::{DayOption, Options},
+    trace, ParseError, Solution,
+};
+use std::collections::VecDeque;
+use validate::{Policy, Readings};
+
+pub mod cli;
+mod generate;
+pub mod report;
+pub mod validate;
+
+/// How often the sum of `window` consecutive readings is larger than the sum
+/// of the window before it. Keeps a running sum instead of adding up every
+/// window again.
+pub fn count_increases(depths: &[u32], window: usize) -> u32 {
+    assert!(window > 0, "a window holds at least one reading");
+    if depths.len() <= window {
+        return 0;
+    }
+
+    let mut sum = depths[..window].iter().map(|&d| d as u64).sum::<u64>();
+    let mut increases = 0;
+    for (&leaving, &entering) in depths.iter().zip(depths[window..].iter()) {
+        let next = sum - leaving as u64 + entering as u64;
+        if next > sum {
+            increases += 1;
+        }
+        sum = next;
+    }
+    trace!(
+        Info,
+        "sonar",
+        "{} of {} windows of {} increased",
+        increases,
+        depths.len() - window + 1,
+        window
+    );
+    increases
+}
+
+/// `count_increases` for readings that arrive one at a time, holding on to
+/// no more than the last `window` of them.
+pub struct WindowCounter {
+    window: usize,
+    recent: VecDeque<u32>,
+    sum: u64,
+    pub increases: u32,
+}
+
+impl WindowCounter {
+    pub fn new(window: usize) -> Self {
+        assert!(window > 0, "a window holds at least one reading");
+        WindowCounter {
+            window,
+            recent: VecDeque::with_capacity(window),
+            sum: 0,
+            increases: 0,
+        }
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    pub fn push(&mut self, depth: u32) {
+        if self.recent.len() < self.window {
+            self.recent.push_back(depth);
+            self.sum += depth as u64;
+            return;
+        }
+        let leaving = self.recent.pop_front().unwrap();
+        self.recent.push_back(depth);
+        let next = self.sum - leaving as u64 + depth as u64;
+        if next > self.sum {
+            self.increases += 1;
+        }
+        self.sum = next;
+    }
+}
+
+pub fn part_1(depths: &[u32]) -> u32 {
+    count_increases(depths, 1)
+}
+
+pub fn part_2(depths: &[u32]) -> u32 {
+    count_increases(depths, 3)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    const EXAMPLE: &'static str = include_str!("../example");
+
+    type Model = Readings;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    /// `--invalid skip|interpolate|fail`, what to do about bad readings.
+    const OPTIONS: &'static [DayOption] = &[DayOption::of::<Policy>("--invalid")];
+
+    fn parse(input: &str) -> Result<Self::Model, ParseError> {
+        Readings::validate(input, Policy::default())
+    }
+
+    fn parse_with(input: &str, options: &Options) -> Result<Self::Model, ParseError> {
+        Readings::validate(input, options.get("--invalid"))
+    }
+
+    fn part_1(readings: &Self::Model) -> Self::Part1 {
+        part_1(&readings.depths)
+    }
+
+    fn part_2(readings: &Self::Model) -> Self::Part2 {
+        part_2(&readings.depths)
+    }
+
+    fn warnings(readings: &Self::Model) -> Vec<String> {
+        readings.summary()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generate::Rng;
+
+    #[test]
+    fn example() {
+        let depths = Day1::parse(Day1::EXAMPLE).unwrap().depths;
+        assert_eq!(part_1(&depths), 7);
+        assert_eq!(part_2(&depths), 5);
+    }
+
+    #[test]
+    fn invalid_option_picks_the_policy() {
+        let input = "100\nx\n102\n";
+        assert!(Day1::parse(input).is_err());
+        let options = Options::default().with("--invalid", "skip");
+        assert_eq!(
+            Day1::parse_with(input, &options).unwrap().depths,
+            [100, 102]
+        );
+        let options = options.with("--invalid", "interpolate");
+        let depths = Day1::parse_with(input, &options).unwrap().depths;
+        assert_eq!(depths, [100, 101, 102]);
+    }
+
+    #[test]
+    fn any_window_matches_summed_windows() {
+        let mut rng = Rng::new(1);
+        let depths = (0..300)
+            .map(|_| rng.between(0, 10_000) as u32)
+            .collect::<Vec<_>>();
+        for window in [1, 2, 3, 5, 10, 299, 300, 400] {
+            let sums = depths
+                .windows(window)
+                .map(|w| w.iter().sum::<u32>())
+                .collect::<Vec<_>>();
+            let expected = sums.windows(2).filter(|w| w[1] > w[0]).count() as u32;
+            assert_eq!(
+                count_increases(&depths, window),
+                expected,
+                "window {}",
+                window
+            );
+        }
+    }
+
+    #[test]
+    fn counter_agrees_with_whole_series() {
+        let mut rng = Rng::new(2);
+        let depths = (0..200)
+            .map(|_| rng.between(0, 1000) as u32)
+            .collect::<Vec<_>>();
+        for window in [1, 3, 7] {
+            let mut counter = WindowCounter::new(window);
+            for (idx, &depth) in depths.iter().enumerate() {
+                counter.push(depth);
+                assert_eq!(counter.increases, count_increases(&depths[..=idx], window));
+            }
+        }
+    }
+}
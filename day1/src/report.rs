@@ -0,0 +1,280 @@
+use std::{fmt::Display, iter};
+
+use common::json::Json;
+
+/// Sums of `window` consecutive readings, kept up to date as the window
+/// slides along. A window of one is the raw series.
+pub fn windowed(depths: &[u32], window: usize) -> impl Iterator<Item = u64> + Clone + '_ {
+    assert!(window > 0, "a window holds at least one reading");
+    let first = depths.iter().take(window).map(|&d| d as u64).sum::<u64>();
+    let start = Some(first).filter(|_| depths.len() >= window);
+    let mut leaving = depths.iter();
+    let mut entering = depths.iter().skip(window);
+    iter::successors(start, move |sum| {
+        let (&leaving, &entering) = (leaving.next()?, entering.next()?);
+        Some(sum - leaving as u64 + entering as u64)
+    })
+}
+
+/// Steps from index `start` to index `end` that all went the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Run {
+    pub fn steps(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+/// The change from index `from` to the one after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Jump {
+    pub from: usize,
+    pub delta: i64,
+}
+
+/// How many deltas fell into each of the equally wide buckets starting at
+/// `low`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    pub low: i64,
+    pub width: i64,
+    pub counts: Vec<usize>,
+}
+
+const BUCKETS: i64 = 10;
+const BAR_WIDTH: usize = 40;
+
+impl Histogram {
+    fn new(low: i64, high: i64, deltas: impl Iterator<Item = i64>) -> Self {
+        let width = ((high - low + 1) + BUCKETS - 1) / BUCKETS;
+        let mut counts = vec![0; ((high - low) / width + 1) as usize];
+        for delta in deltas {
+            counts[((delta - low) / width) as usize] += 1;
+        }
+        Histogram { low, width, counts }
+    }
+
+    /// First and last delta each bucket holds.
+    pub fn buckets(&self) -> impl Iterator<Item = (i64, i64, usize)> + '_ {
+        self.counts.iter().enumerate().map(move |(idx, &count)| {
+            let low = self.low + idx as i64 * self.width;
+            (low, low + self.width - 1, count)
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub window: usize,
+    /// Number of values in the series, i.e. readings or windows.
+    pub len: usize,
+    pub longest_increase: Option<Run>,
+    pub longest_decrease: Option<Run>,
+    /// The step that changed the most, in either direction.
+    pub largest_jump: Option<Jump>,
+    /// How far the series went down in total, i.e. the sum of its increases
+    /// in depth.
+    pub total_descent: u64,
+    pub histogram: Option<Histogram>,
+}
+
+fn deltas(series: impl Iterator<Item = u64> + Clone) -> impl Iterator<Item = i64> {
+    series
+        .clone()
+        .zip(series.skip(1))
+        .map(|(a, b)| b as i64 - a as i64)
+}
+
+fn longer(best: Option<Run>, run: Run) -> Option<Run> {
+    match best {
+        Some(best) if best.steps() >= run.steps() => Some(best),
+        _ if run.steps() > 0 => Some(run),
+        _ => best,
+    }
+}
+
+impl Report {
+    /// Looks at the series of sums of `window` readings, without collecting
+    /// it anywhere.
+    pub fn new(depths: &[u32], window: usize) -> Self {
+        let series = windowed(depths, window);
+        let mut report = Report {
+            window,
+            len: series.clone().count(),
+            longest_increase: None,
+            longest_decrease: None,
+            largest_jump: None,
+            total_descent: 0,
+            histogram: None,
+        };
+
+        let mut increase = Run { start: 0, end: 0 };
+        let mut decrease = Run { start: 0, end: 0 };
+        let mut range: Option<(i64, i64)> = None;
+        for (from, delta) in deltas(series.clone()).enumerate() {
+            let to = from + 1;
+            if delta > 0 {
+                increase.end = to;
+                report.total_descent += delta as u64;
+            } else {
+                increase = Run { start: to, end: to };
+            }
+            if delta < 0 {
+                decrease.end = to;
+            } else {
+                decrease = Run { start: to, end: to };
+            }
+            report.longest_increase = longer(report.longest_increase, increase);
+            report.longest_decrease = longer(report.longest_decrease, decrease);
+
+            if report
+                .largest_jump
+                .is_none_or(|jump| delta.abs() > jump.delta.abs())
+            {
+                report.largest_jump = Some(Jump { from, delta });
+            }
+            range = Some(match range {
+                Some((low, high)) => (low.min(delta), high.max(delta)),
+                None => (delta, delta),
+            });
+        }
+        report.histogram = range.map(|(low, high)| Histogram::new(low, high, deltas(series)));
+        report
+    }
+
+    pub fn to_json(&self) -> Json {
+        let run = |run: Option<Run>| match run {
+            Some(run) => Json::Object(vec![
+                ("start", Json::number(run.start)),
+                ("end", Json::number(run.end)),
+                ("steps", Json::number(run.steps())),
+            ]),
+            None => Json::Null,
+        };
+        let jump = match self.largest_jump {
+            Some(jump) => Json::Object(vec![
+                ("from", Json::number(jump.from)),
+                ("to", Json::number(jump.from + 1)),
+                ("delta", Json::number(jump.delta)),
+            ]),
+            None => Json::Null,
+        };
+        let histogram = self
+            .histogram
+            .iter()
+            .flat_map(Histogram::buckets)
+            .map(|(low, high, count)| {
+                Json::Object(vec![
+                    ("low", Json::number(low)),
+                    ("high", Json::number(high)),
+                    ("count", Json::number(count)),
+                ])
+            })
+            .collect();
+        Json::Object(vec![
+            ("window", Json::number(self.window)),
+            ("len", Json::number(self.len)),
+            ("longest_increase", run(self.longest_increase)),
+            ("longest_decrease", run(self.longest_decrease)),
+            ("largest_jump", jump),
+            ("total_descent", Json::number(self.total_descent)),
+            ("histogram", Json::Array(histogram)),
+        ])
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.window {
+            1 => writeln!(f, "{} readings", self.len)?,
+            window => writeln!(f, "{} windows of {} readings", self.len, window)?,
+        }
+        for (name, run) in [
+            ("increase", self.longest_increase),
+            ("decrease", self.longest_decrease),
+        ] {
+            match run {
+                Some(run) => writeln!(
+                    f,
+                    "  longest {}: {} steps, indices {}..={}",
+                    name,
+                    run.steps(),
+                    run.start,
+                    run.end
+                )?,
+                None => writeln!(f, "  longest {}: none", name)?,
+            }
+        }
+        if let Some(jump) = self.largest_jump {
+            writeln!(
+                f,
+                "  largest jump: {:+} from index {} to {}",
+                jump.delta,
+                jump.from,
+                jump.from + 1
+            )?;
+        }
+        writeln!(f, "  total descent: {}", self.total_descent)?;
+
+        if let Some(histogram) = &self.histogram {
+            writeln!(f, "  deltas:")?;
+            let most = histogram.counts.iter().max().copied().unwrap_or(0).max(1);
+            for (low, high, count) in histogram.buckets() {
+                let bar = "#".repeat((count * BAR_WIDTH).div_ceil(most));
+                let line = format!("  {:>8}..={:<8}{:>8} {}", low, high, count, bar);
+                writeln!(f, "{}", line.trim_end())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day1;
+    use common::Solution;
+
+    #[test]
+    fn raw_series() {
+        let depths = Day1::parse(Day1::EXAMPLE).unwrap().depths;
+        let report = Report::new(&depths, 1);
+        assert_eq!(report.len, 10);
+        assert_eq!(report.longest_increase, Some(Run { start: 0, end: 3 }));
+        assert_eq!(report.longest_decrease, Some(Run { start: 3, end: 4 }));
+        assert_eq!(report.largest_jump, Some(Jump { from: 5, delta: 33 }));
+        assert_eq!(report.total_descent, 83);
+
+        let histogram = report.histogram.unwrap();
+        assert_eq!((histogram.low, histogram.width), (-10, 5));
+        assert_eq!(histogram.counts, vec![2, 0, 3, 2, 0, 0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn windowed_series() {
+        let depths = Day1::parse(Day1::EXAMPLE).unwrap().depths;
+        assert_eq!(
+            windowed(&depths, 3).collect::<Vec<_>>(),
+            vec![607, 618, 618, 617, 647, 716, 769, 792]
+        );
+
+        let report = Report::new(&depths, 3);
+        assert_eq!(report.longest_increase, Some(Run { start: 3, end: 7 }));
+        assert_eq!(report.longest_decrease, Some(Run { start: 2, end: 3 }));
+        assert_eq!(report.largest_jump, Some(Jump { from: 4, delta: 69 }));
+        assert_eq!(report.total_descent, 186);
+    }
+
+    #[test]
+    fn short_series() {
+        assert_eq!(windowed(&[1, 2], 3).count(), 0);
+        let report = Report::new(&[5], 1);
+        assert_eq!(report.len, 1);
+        assert_eq!(report.longest_increase, None);
+        assert_eq!(report.histogram, None);
+    }
+}
@@ -0,0 +1,301 @@
+use std::{fmt::Display, iter, str::FromStr};
+
+use common::{grammar::number, ParseError, Span};
+
+/// What to do about readings that aren't depths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Policy {
+    /// Stop at the first one, like any other parse error.
+    #[default]
+    Fail,
+    /// Leave them out of the series.
+    Skip,
+    /// Replace them with depths on a straight line between the readings
+    /// around them.
+    Interpolate,
+}
+
+impl FromStr for Policy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fail" => Ok(Policy::Fail),
+            "skip" => Ok(Policy::Skip),
+            "interpolate" => Ok(Policy::Interpolate),
+            _ => Err(format!(
+                "unknown policy {}, expected skip, interpolate or fail",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for Policy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Policy::Fail => write!(f, "failed"),
+            Policy::Skip => write!(f, "skipped"),
+            Policy::Interpolate => write!(f, "interpolated"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Fault {
+    Blank,
+    Negative,
+    TooDeep,
+    NotANumber,
+}
+
+impl Fault {
+    fn of(text: &str) -> Self {
+        let digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+        if text.trim().is_empty() {
+            Fault::Blank
+        } else if text.strip_prefix('-').is_some_and(digits) {
+            Fault::Negative
+        } else if digits(text) {
+            Fault::TooDeep
+        } else {
+            Fault::NotANumber
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Fault::Blank => "blank",
+            Fault::Negative => "negative",
+            Fault::TooDeep => "too deep",
+            Fault::NotANumber => "not a number",
+        }
+    }
+}
+
+/// A reading that isn't a depth, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub line: usize,
+    pub fault: Fault,
+    pub text: String,
+}
+
+impl Problem {
+    /// Classifies a line, returning the depth if it is one.
+    pub fn check(line: usize, text: &str) -> Result<u32, (Problem, ParseError)> {
+        number("depth reading")(Span::new(text)).map_err(|err| {
+            let problem = Problem {
+                line,
+                fault: Fault::of(text),
+                text: String::from(text),
+            };
+            (problem, ParseError { line, ..err })
+        })
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: {} reading {:?}",
+            self.line,
+            self.fault.name(),
+            self.text
+        )
+    }
+}
+
+/// `gap` depths between `before` and `after`, on a straight line between
+/// them. A gap at either end of the series takes the one reading it has.
+pub fn fill(before: Option<u32>, after: Option<u32>, gap: usize) -> impl Iterator<Item = u32> {
+    let steps = gap as u64 + 1;
+    (1..=gap as u64).filter_map(move |step| match (before, after) {
+        (Some(a), Some(b)) => {
+            let (a, b) = (a as u64, b as u64);
+            Some(((a * (steps - step) + b * step + steps / 2) / steps) as u32)
+        }
+        (Some(depth), None) | (None, Some(depth)) => Some(depth),
+        (None, None) => None,
+    })
+}
+
+/// The depths that made it through validation, and what happened to the
+/// ones that didn't.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Readings {
+    pub depths: Vec<u32>,
+    pub problems: Vec<Problem>,
+    pub policy: Policy,
+}
+
+impl Readings {
+    pub fn validate(input: &str, policy: Policy) -> Result<Self, ParseError> {
+        let mut depths = Vec::new();
+        let mut problems = Vec::new();
+        let mut gap = 0;
+        for (idx, text) in input.lines().enumerate() {
+            match Problem::check(idx + 1, text) {
+                Ok(depth) => {
+                    if policy == Policy::Interpolate {
+                        depths.extend(fill(depths.last().copied(), Some(depth), gap));
+                    }
+                    gap = 0;
+                    depths.push(depth);
+                }
+                Err((_, err)) if policy == Policy::Fail => return Err(err),
+                Err((problem, _)) => {
+                    problems.push(problem);
+                    gap += 1;
+                }
+            }
+        }
+        if policy == Policy::Interpolate {
+            depths.extend(fill(depths.last().copied(), None, gap));
+        }
+
+        Ok(Readings {
+            depths,
+            problems,
+            policy,
+        })
+    }
+
+    /// One line per kind of problem, e.g. `2 blank readings skipped (lines
+    /// 4, 9)`.
+    pub fn summary(&self) -> Vec<String> {
+        let mut faults = self.problems.iter().map(|p| p.fault).collect::<Vec<_>>();
+        faults.sort();
+        faults.dedup();
+        faults
+            .into_iter()
+            .map(|fault| {
+                let lines = self
+                    .problems
+                    .iter()
+                    .filter(|p| p.fault == fault)
+                    .map(|p| p.line.to_string())
+                    .collect::<Vec<_>>();
+                format!(
+                    "{} {} reading{} {} (line{} {})",
+                    lines.len(),
+                    fault.name(),
+                    if lines.len() == 1 { "" } else { "s" },
+                    self.policy,
+                    if lines.len() == 1 { "" } else { "s" },
+                    lines.join(", ")
+                )
+            })
+            .collect()
+    }
+}
+
+/// Bad readings waiting for the next good one, so gaps in a stream can be
+/// interpolated while holding on to nothing but the last depth.
+#[derive(Default)]
+pub struct Gap {
+    last: Option<u32>,
+    start: usize,
+    len: usize,
+}
+
+impl Gap {
+    pub fn open(&mut self, line: usize) {
+        if self.len == 0 {
+            self.start = line;
+        }
+        self.len += 1;
+    }
+
+    /// Line numbers and depths to report now that `depth` arrived on `line`.
+    pub fn close(&mut self, line: usize, depth: u32) -> impl Iterator<Item = (usize, u32)> {
+        let filled = fill(self.last, Some(depth), self.len);
+        let lines = self.start..self.start + self.len;
+        self.last = Some(depth);
+        self.len = 0;
+        lines.zip(filled).chain(iter::once((line, depth)))
+    }
+
+    /// What's left of the gap once the stream ends.
+    pub fn finish(&mut self) -> impl Iterator<Item = (usize, u32)> {
+        let filled = fill(self.last, None, self.len);
+        let lines = self.start..self.start + self.len;
+        self.len = 0;
+        lines.zip(filled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "100\n\n120\n-5\n130\nabc\n99999999999\n160\n170\n";
+
+    #[test]
+    fn classifies_bad_readings() {
+        let readings = Readings::validate(INPUT, Policy::Skip).unwrap();
+        let faults = readings
+            .problems
+            .iter()
+            .map(|p| (p.line, p.fault))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            faults,
+            vec![
+                (2, Fault::Blank),
+                (4, Fault::Negative),
+                (6, Fault::NotANumber),
+                (7, Fault::TooDeep),
+            ]
+        );
+        assert_eq!(readings.depths, vec![100, 120, 130, 160, 170]);
+        assert_eq!(
+            readings.summary(),
+            vec![
+                "1 blank reading skipped (line 2)",
+                "1 negative reading skipped (line 4)",
+                "1 too deep reading skipped (line 7)",
+                "1 not a number reading skipped (line 6)",
+            ]
+        );
+    }
+
+    #[test]
+    fn interpolates_between_neighbours() {
+        let readings = Readings::validate(INPUT, Policy::Interpolate).unwrap();
+        assert_eq!(
+            readings.depths,
+            vec![100, 110, 120, 125, 130, 140, 150, 160, 170]
+        );
+
+        let edges = Readings::validate("x\n5\n6\n\n", Policy::Interpolate).unwrap();
+        assert_eq!(edges.depths, vec![5, 5, 6, 6]);
+    }
+
+    #[test]
+    fn fail_points_at_the_first_problem() {
+        let err = Readings::validate(INPUT, Policy::Fail).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "depth reading");
+    }
+
+    #[test]
+    fn stream_gaps_match_whole_input() {
+        let mut gap = Gap::default();
+        let mut depths = vec![];
+        for (idx, text) in INPUT.lines().enumerate() {
+            match Problem::check(idx + 1, text) {
+                Ok(depth) => depths.extend(gap.close(idx + 1, depth)),
+                Err(_) => gap.open(idx + 1),
+            }
+        }
+        depths.extend(gap.finish());
+        let expected = Readings::validate(INPUT, Policy::Interpolate).unwrap();
+        assert_eq!(
+            depths.iter().map(|&(_, d)| d).collect::<Vec<_>>(),
+            expected.depths
+        );
+        assert_eq!(depths[1], (2, 110));
+    }
+}
@@ -0,0 +1,87 @@
+use common::generate::{Generated, Generator, Rng};
+
+use crate::Day10;
+
+const OPEN: [char; 4] = ['(', '[', '{', '<'];
+const CLOSE: [char; 4] = [')', ']', '}', '>'];
+const ERROR_SCORE: [usize; 4] = [3, 57, 1197, 25137];
+/// Keeps completion scores well inside a `usize`.
+const MAX_DEPTH: usize = 20;
+
+/// Random balanced-so-far chunks, returning the line and its open chunks.
+fn chunks(rng: &mut Rng, len: usize) -> (String, Vec<usize>) {
+    let mut line = String::new();
+    let mut open = vec![];
+    for _ in 0..len {
+        if !open.is_empty() && (open.len() >= MAX_DEPTH || rng.chance(2, 5)) {
+            line.push(CLOSE[open.pop().unwrap()]);
+        } else {
+            let chunk = rng.below(4);
+            open.push(chunk);
+            line.push(OPEN[chunk]);
+        }
+    }
+    if open.is_empty() {
+        let chunk = rng.below(4);
+        open.push(chunk);
+        line.push(OPEN[chunk]);
+    }
+    (line, open)
+}
+
+impl Generator for Day10 {
+    /// Number of lines.
+    const DEFAULT_SIZE: usize = 100;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        // part 2 needs an odd number of incomplete lines
+        let mut corrupted = (0..size.max(1))
+            .map(|_| rng.chance(1, 2))
+            .collect::<Vec<_>>();
+        if corrupted.iter().filter(|&&c| !c).count() % 2 == 0 {
+            corrupted[0] = !corrupted[0];
+        }
+
+        let mut input = String::new();
+        let mut error_score = 0;
+        let mut completion_scores = vec![];
+        for corrupted in corrupted {
+            let len = rng.between(10, 100);
+            let (mut line, open) = chunks(rng, len);
+            if corrupted {
+                let expected = *open.last().unwrap();
+                let found = (expected + rng.between(1, 3)) % 4;
+                line.push(CLOSE[found]);
+                error_score += ERROR_SCORE[found];
+                let len = rng.below(20);
+                let (rest, _) = chunks(rng, len);
+                line += &rest;
+            } else {
+                let score = open.iter().rev().fold(0, |acc, chunk| acc * 5 + chunk + 1);
+                completion_scores.push(score);
+            }
+            input += &line;
+            input += "\n";
+        }
+        completion_scores.sort_unstable();
+
+        Generated {
+            input,
+            part_1: Some(error_score.into()),
+            part_2: Some(completion_scores[completion_scores.len() / 2].into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generate::check;
+
+    #[test]
+    fn generated_answers_match() {
+        for seed in 0..10 {
+            assert_eq!(check::<Day10>(seed, 50), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use common::{
+    arith::{Arithmetic, Checked, Overflow},
+    grammar::{self, chars, lines, verify},
+    trace, Answer, ParseError, Solution,
+};
+
+mod generate;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Chunk {
+    Paren,
+    Bracket,
+    Curly,
+    Angle,
+}
+
+impl Chunk {
+    pub fn get_invalid_score(&self) -> usize {
+        match self {
+            Chunk::Paren => 3,
+            Chunk::Bracket => 57,
+            Chunk::Curly => 1197,
+            Chunk::Angle => 25137,
+        }
+    }
+
+    pub fn get_autocomplete_score(&self) -> usize {
+        match self {
+            Chunk::Paren => 1,
+            Chunk::Bracket => 2,
+            Chunk::Curly => 3,
+            Chunk::Angle => 4,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Mark {
+    Open(Chunk),
+    Close(Chunk),
+}
+
+impl Mark {
+    pub fn from_char(c: char) -> Option<Mark> {
+        match c {
+            '(' => Some(Mark::Open(Chunk::Paren)),
+            '[' => Some(Mark::Open(Chunk::Bracket)),
+            '{' => Some(Mark::Open(Chunk::Curly)),
+            '<' => Some(Mark::Open(Chunk::Angle)),
+            ')' => Some(Mark::Close(Chunk::Paren)),
+            ']' => Some(Mark::Close(Chunk::Bracket)),
+            '}' => Some(Mark::Close(Chunk::Curly)),
+            '>' => Some(Mark::Close(Chunk::Angle)),
+            _ => None,
+        }
+    }
+}
+
+pub fn part_1(lines: &[Vec<Mark>]) -> usize {
+    let mut score = 0;
+
+    'line: for (idx, line) in lines.iter().enumerate() {
+        let mut stack = vec![];
+        for (column, mark) in line.iter().enumerate() {
+            match mark {
+                Mark::Open(chunk) => stack.push(*chunk),
+                Mark::Close(chunk) => {
+                    if let Some(c) = stack.pop() {
+                        if c != *chunk {
+                            trace!(
+                                Debug,
+                                "syntax",
+                                "line {} corrupted at column {}, scoring {}",
+                                idx + 1,
+                                column + 1,
+                                chunk.get_invalid_score()
+                            );
+                            score += chunk.get_invalid_score();
+                            continue 'line;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    score
+}
+
+/// The chunks each incomplete line leaves open, outermost first, along with
+/// the index of the line.
+fn incomplete(lines: &[Vec<Mark>]) -> Vec<(usize, Vec<Chunk>)> {
+    let mut incomplete = vec![];
+
+    'line: for (idx, line) in lines.iter().enumerate() {
+        let mut stack = vec![];
+        for mark in line.iter() {
+            match mark {
+                Mark::Open(chunk) => stack.push(*chunk),
+                Mark::Close(chunk) => {
+                    if let Some(c) = stack.pop() {
+                        if c != *chunk {
+                            // invalid line, skip
+                            continue 'line;
+                        }
+                    }
+                }
+            }
+        }
+        incomplete.push((idx, stack));
+    }
+    incomplete
+}
+
+pub fn part_2(lines: &[Vec<Mark>]) -> usize {
+    let mut complete_scores = incomplete(lines)
+        .iter()
+        .map(|(idx, stack)| {
+            let score = stack
+                .iter()
+                .rev()
+                .map(|c| c.get_autocomplete_score())
+                .fold(0, |acc, score| acc * 5 + score);
+            trace!(
+                Debug,
+                "syntax",
+                "line {} needs {} closing marks, scoring {}",
+                idx + 1,
+                stack.len(),
+                score
+            );
+            score
+        })
+        .collect::<Vec<_>>();
+
+    complete_scores.sort();
+    let middle = complete_scores[(complete_scores.len() - 1) / 2];
+    trace!(
+        Info,
+        "syntax",
+        "{} incomplete lines, middle score {}",
+        complete_scores.len(),
+        middle
+    );
+    middle
+}
+
+/// `part_2`, failing on the first line whose score doesn't fit instead of
+/// wrapping around.
+pub fn checked_part_2(lines: &[Vec<Mark>]) -> Result<usize, Overflow> {
+    let mut complete_scores = incomplete(lines)
+        .iter()
+        .map(|(idx, stack)| {
+            stack
+                .iter()
+                .rev()
+                .map(|c| c.get_autocomplete_score())
+                .try_fold(0usize, |acc, score| acc.try_mul(5)?.try_add(score))
+                .map_err(|overflow| overflow.at(format!("line {}", idx + 1)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    complete_scores.sort();
+    Ok(complete_scores[(complete_scores.len() - 1) / 2])
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const EXAMPLE: &'static str = include_str!("../example");
+
+    type Model = Vec<Vec<Mark>>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    /// Part 2 takes the middle score of the incomplete lines, so there has
+    /// to be at least one.
+    fn parse(input: &str) -> Result<Self::Model, ParseError> {
+        let marks = lines(chars("one of `()[]{}<>`", Mark::from_char));
+        let marks = verify(marks, "at least one incomplete line", |lines| {
+            !incomplete(lines).is_empty()
+        });
+        grammar::parse(input, marks)
+    }
+
+    fn part_1(lines: &Self::Model) -> Self::Part1 {
+        part_1(lines)
+    }
+
+    fn part_2(lines: &Self::Model) -> Self::Part2 {
+        part_2(lines)
+    }
+
+    fn solve(lines: &Self::Model, part: u8, _: Arithmetic) -> Result<Answer, Overflow> {
+        match part {
+            1 => Ok(part_1(lines).into()),
+            _ => checked_part_2(lines).map(Answer::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let lines = Day10::parse(Day10::EXAMPLE).unwrap();
+        assert_eq!(part_1(&lines), 26397);
+        assert_eq!(part_2(&lines), 288957);
+        assert_eq!(checked_part_2(&lines), Ok(288957));
+    }
+
+    #[test]
+    fn reports_bad_mark() {
+        let input = "[()]\n{(]>\n(a)\n";
+        let err = Day10::parse(input).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 2: expected one of `()[]{}<>`, found \"a\""
+        );
+    }
+
+    #[test]
+    fn needs_an_incomplete_line() {
+        for input in ["(]\n", "", "{()()()>\n(((()))}\n"] {
+            let err = Day10::parse(input).err().unwrap();
+            assert_eq!((err.line, err.column), (1, 1));
+            assert_eq!(err.expected, "at least one incomplete line");
+        }
+    }
+
+    #[test]
+    fn checked_scores_report_the_line() {
+        let input = format!("[<\n{}\n", "(".repeat(30));
+        let lines = Day10::parse(&input).unwrap();
+        let overflow = checked_part_2(&lines).unwrap_err();
+        assert_eq!(overflow.operator, '*');
+        assert_eq!(overflow.context.as_deref(), Some("line 2"));
+    }
+}
@@ -0,0 +1,62 @@
+use common::{
+    generate::{Generated, Generator, Rng},
+    Simulation, Solution,
+};
+
+use crate::{Day11, GRID_SIZE};
+
+/// Steps a candidate grid gets to synchronise before it is thrown away.
+const MAX_STEPS: usize = 1000;
+
+fn random_grid(rng: &mut Rng) -> String {
+    (0..GRID_SIZE)
+        .map(|_| {
+            let row = (0..GRID_SIZE)
+                .map(|_| rng.below(10).to_string())
+                .collect::<String>();
+            row + "\n"
+        })
+        .collect()
+}
+
+impl Generator for Day11 {
+    /// Unused: the puzzle fixes the grid at 10x10.
+    const DEFAULT_SIZE: usize = GRID_SIZE;
+
+    /// Random energy levels. Plenty of grids never flash all at once, which
+    /// would leave part 2 looping forever, so those are drawn again. Flash
+    /// counts can't be worked out without running the cascade, so there are
+    /// no known answers.
+    fn generate(rng: &mut Rng, _size: usize) -> Generated {
+        loop {
+            let input = random_grid(rng);
+            let mut grid = Day11::parse(&input).expect("generated grid parses");
+            let mut steps = 0;
+            let synchronised = grid.run_until(|grid| {
+                steps += 1;
+                grid.flashed.len() == GRID_SIZE * GRID_SIZE || steps == MAX_STEPS
+            }) < MAX_STEPS;
+
+            if synchronised {
+                return Generated {
+                    input,
+                    part_1: None,
+                    part_2: None,
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generate::check;
+
+    #[test]
+    fn generated_grids_synchronise() {
+        for seed in 0..5 {
+            assert_eq!(check::<Day11>(seed, GRID_SIZE), Ok(()));
+        }
+    }
+}
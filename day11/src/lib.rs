@@ -0,0 +1,289 @@
+use common::{
+    snapshot::{Simulate, Snapshot},
+    trace, Answer, ParseError, Simulation, Solution, Span,
+};
+use grid::{Grid as Cells, Pos};
+
+mod generate;
+pub mod visual;
+
+pub const GRID_SIZE: usize = 10;
+
+#[derive(Clone)]
+pub struct Grid {
+    pub flashed: Vec<Pos>,
+    pub flash_count: usize,
+    values: Cells<u8>,
+}
+
+impl Grid {
+    fn parse(input: Span) -> Result<Self, ParseError> {
+        let values = Cells::parse_digits(input)?;
+        if values.width() != GRID_SIZE || values.height() != GRID_SIZE {
+            return Err(input.error("10x10 grid of energy levels"));
+        }
+
+        Ok(Self {
+            flashed: vec![],
+            values,
+            flash_count: 0,
+        })
+    }
+
+    /// Flashes the octopus at `pos` and returns the neighbours it pushed over
+    /// the edge.
+    fn flash(&mut self, pos: Pos) -> Vec<Pos> {
+        self.flash_count += 1;
+        self.flashed.push(pos);
+
+        let mut triggered = vec![];
+        for adjacent in self.values.neighbours8(pos) {
+            let octopus_value = &mut self.values[adjacent];
+            if *octopus_value <= 9 {
+                *octopus_value += 1;
+                if *octopus_value > 9 {
+                    triggered.push(adjacent);
+                }
+            }
+        }
+        triggered
+    }
+}
+
+impl Simulation for Grid {
+    fn step(&mut self) {
+        // reset flashed
+        self.flashed = vec![];
+
+        // increase all octopuses
+        for v in self.values.iter_mut() {
+            *v += 1;
+        }
+
+        // flash until no octopus is triggered
+        let mut pending = self
+            .values
+            .enumerate()
+            .filter(|(_, &v)| v > 9)
+            .map(|(pos, _)| pos)
+            .collect::<Vec<_>>();
+        while let Some(pos) = pending.pop() {
+            let triggered = self.flash(pos);
+            pending.extend(triggered);
+        }
+
+        // reset all that flashed
+        for &flashed in self.flashed.iter() {
+            self.values[flashed] = 0;
+        }
+        trace!(
+            Debug,
+            "octopus",
+            "{} flashed, {} flashes so far",
+            self.flashed.len(),
+            self.flash_count
+        );
+    }
+}
+
+impl Snapshot for Grid {
+    /// A `flashes <n>` line followed by the energy levels.
+    fn save(&self, out: &mut String) {
+        out.push_str(&format!("flashes {}\n{}", self.flash_count, self.values));
+    }
+
+    fn restore(input: Span) -> Result<Self, ParseError> {
+        let (line, levels) = input
+            .split_once("\n")
+            .ok_or_else(|| input.error("`flashes <n>` line"))?;
+        let flash_count = line
+            .strip_prefix("flashes ")
+            .ok_or_else(|| line.error("`flashes <n>` line"))?
+            .parse("flash count")?;
+        Ok(Grid {
+            flash_count,
+            ..Grid::parse(levels)?
+        })
+    }
+}
+
+pub fn part_1(grid: &Grid) -> usize {
+    let mut grid = grid.clone();
+    grid.run(100);
+    trace!(Info, "octopus", "{} flashes in 100 steps", grid.flash_count);
+    grid.flash_count
+}
+
+pub fn part_2(grid: &Grid) -> usize {
+    let mut grid = grid.clone();
+    let step = grid.run_until(|grid| grid.flashed.len() == GRID_SIZE * GRID_SIZE);
+    trace!(Info, "octopus", "all flashed together on step {}", step);
+    step
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const EXAMPLE: &'static str = include_str!("../example");
+
+    type Model = Grid;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Model, ParseError> {
+        Grid::parse(Span::new(input))
+    }
+
+    fn part_1(grid: &Self::Model) -> Self::Part1 {
+        part_1(grid)
+    }
+
+    fn part_2(grid: &Self::Model) -> Self::Part2 {
+        part_2(grid)
+    }
+}
+
+impl Simulate for Day11 {
+    type State = Grid;
+
+    fn start(grid: &Self::Model) -> Self::State {
+        grid.clone()
+    }
+
+    fn summary(grid: &Self::State) -> Answer {
+        grid.flash_count.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{property, snapshot, Cycle};
+
+    type Levels = [[u8; GRID_SIZE]; GRID_SIZE];
+
+    /// Rescans the whole grid until nothing else flashes and returns how
+    /// many octopuses flashed.
+    fn naive_step(levels: &mut Levels) -> usize {
+        let mut flashed = [[false; GRID_SIZE]; GRID_SIZE];
+        for level in levels.iter_mut().flatten() {
+            *level += 1;
+        }
+        let mut count = 0;
+        loop {
+            let before = count;
+            for y in 0..GRID_SIZE {
+                for x in 0..GRID_SIZE {
+                    if levels[y][x] <= 9 || flashed[y][x] {
+                        continue;
+                    }
+                    flashed[y][x] = true;
+                    count += 1;
+                    let (xs, ys) = (x.saturating_sub(1)..x + 2, y.saturating_sub(1)..y + 2);
+                    for row in levels.iter_mut().take(ys.end).skip(ys.start) {
+                        for level in row.iter_mut().take(xs.end).skip(xs.start) {
+                            *level += 1;
+                        }
+                    }
+                }
+            }
+            if count == before {
+                break;
+            }
+        }
+        for (level, flashed) in levels.iter_mut().flatten().zip(flashed.iter().flatten()) {
+            if *flashed {
+                *level = 0;
+            }
+        }
+        count
+    }
+
+    fn values(grid: &Grid) -> Vec<u8> {
+        grid.values.iter().copied().collect()
+    }
+
+    #[test]
+    fn example() {
+        let grid = Day11::parse(Day11::EXAMPLE).unwrap();
+        assert_eq!(part_1(&grid), 1656);
+        assert_eq!(part_2(&grid), 195);
+    }
+
+    #[test]
+    fn snapshot_resumes_where_it_stopped() {
+        let grid = Day11::parse(Day11::EXAMPLE).unwrap();
+        let mut grid = Day11::start(&grid);
+        grid.run(60);
+        let text = snapshot::write::<Day11>(60, &grid);
+        let (step, mut resumed) = snapshot::read::<Day11>(&text).unwrap();
+        assert_eq!(step, 60);
+        assert_eq!(snapshot::write::<Day11>(step, &resumed), text);
+        resumed.run(40);
+        assert_eq!(resumed.flash_count, 1656);
+    }
+
+    #[test]
+    fn step_cascades_flashes() {
+        let mut grid = Day11::parse(Day11::EXAMPLE).unwrap();
+
+        grid.step();
+        assert_eq!(grid.flash_count, 0);
+
+        grid.step();
+        let expected = Day11::parse(
+            "8807476555\n5089087054\n8597889608\n8485769600\n8700908800\n\
+             6600088989\n6800005943\n0000007456\n9000000876\n8700006848\n",
+        )
+        .unwrap();
+        assert_eq!(values(&grid), values(&expected));
+        assert_eq!(grid.flash_count, 35);
+        assert_eq!(grid.flashed.len(), 35);
+
+        for _ in 2..10 {
+            grid.step();
+        }
+        assert_eq!(grid.flash_count, 204);
+    }
+
+    #[test]
+    fn synchronised_flashes_cycle() {
+        let mut grid = Day11::parse(Day11::EXAMPLE).unwrap();
+        let cycle = grid.find_cycle(1000, |grid| grid.values.clone()).unwrap();
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 195,
+                len: 10
+            }
+        );
+        assert_eq!(grid.flashed.len(), GRID_SIZE * GRID_SIZE);
+    }
+
+    #[test]
+    fn matches_naive_simulation() {
+        property::check(50, |rng| {
+            let mut levels = [[0; GRID_SIZE]; GRID_SIZE];
+            for level in levels.iter_mut().flatten() {
+                *level = rng.below(10) as u8;
+            }
+            let input = levels
+                .iter()
+                .map(|row| row.iter().map(|l| l.to_string()).collect::<String>() + "\n")
+                .collect::<String>();
+            let grid = Day11::parse(&input).unwrap();
+
+            let mut stepped = grid.clone();
+            let mut total = 0;
+            for _ in 0..100 {
+                let flashes = naive_step(&mut levels);
+                stepped.step();
+                assert_eq!(stepped.flashed.len(), flashes);
+                assert_eq!(values(&stepped), levels.concat());
+                total += flashes;
+            }
+            assert_eq!(part_1(&grid), total);
+        });
+    }
+}
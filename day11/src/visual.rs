@@ -0,0 +1,74 @@
+use common::Simulation;
+use grid::{
+    image::Rgb,
+    visual::{Cell, Scene, Visual},
+    Grid as Cells,
+};
+
+use crate::{Day11, Grid, GRID_SIZE};
+
+/// Energy ramps from dark blue up to white as octopuses charge.
+const ENERGY: [u8; 10] = [17, 18, 19, 20, 21, 27, 33, 39, 45, 51];
+
+/// Runs `Grid::step` once per frame until every octopus flashes together,
+/// lighting up the ones that flashed in that step.
+pub struct Flashes {
+    grid: Grid,
+    steps: usize,
+}
+
+impl Flashes {
+    pub fn new(grid: Grid) -> Self {
+        Flashes { grid, steps: 0 }
+    }
+
+    fn synchronised(&self) -> bool {
+        self.grid.flashed.len() == GRID_SIZE * GRID_SIZE
+    }
+}
+
+impl Scene for Flashes {
+    fn frame(&self) -> Cells<Cell> {
+        let mut frame = self.grid.values.map(|_, &level| {
+            let digit = std::char::from_digit(level as u32, 10).unwrap_or('*');
+            Cell::new(digit, ENERGY[(level as usize).min(9)])
+        });
+        for &pos in self.grid.flashed.iter() {
+            frame[pos] = Cell::new('█', 226);
+        }
+        frame
+    }
+
+    fn advance(&mut self) -> bool {
+        if self.synchronised() {
+            return false;
+        }
+        self.grid.step();
+        self.steps += 1;
+        true
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "step {}, {} flashed, {} flashes so far",
+            self.steps,
+            self.grid.flashed.len(),
+            self.grid.flash_count
+        )
+    }
+}
+
+impl Visual for Day11 {
+    type Scene = Flashes;
+
+    fn scene(grid: Self::Model) -> Self::Scene {
+        Flashes::new(grid)
+    }
+
+    /// Energy levels after the 100 steps of part 1, brighter when higher.
+    fn image(grid: &Self::Model) -> Cells<Rgb> {
+        let mut grid = grid.clone();
+        grid.run(100);
+        grid.values.map(|_, &level| Rgb::grey(level * 28))
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use common::generate::{Generated, Generator, Rng};
+
+use crate::Day12;
+
+struct Caves {
+    names: Vec<String>,
+    adjacent: Vec<Vec<usize>>,
+}
+
+impl Caves {
+    fn is_small(&self, cave: usize) -> bool {
+        self.names[cave].chars().all(|c| c.is_lowercase())
+    }
+
+    /// Counts paths to `end` by memoising on the set of small caves seen so
+    /// far, rather than walking every path.
+    fn paths(
+        &self,
+        cave: usize,
+        seen: u64,
+        revisit: bool,
+        memo: &mut HashMap<(usize, u64, bool), usize>,
+    ) -> usize {
+        if self.names[cave] == "end" {
+            return 1;
+        }
+        if let Some(&count) = memo.get(&(cave, seen, revisit)) {
+            return count;
+        }
+        let mut count = 0;
+        for &next in self.adjacent[cave].iter() {
+            if self.names[next] == "start" {
+                continue;
+            } else if !self.is_small(next) {
+                count += self.paths(next, seen, revisit, memo);
+            } else if seen & 1 << next == 0 {
+                count += self.paths(next, seen | 1 << next, revisit, memo);
+            } else if revisit {
+                count += self.paths(next, seen, false, memo);
+            }
+        }
+        memo.insert((cave, seen, revisit), count);
+        count
+    }
+}
+
+fn name(rng: &mut Rng, names: &[String], letters: std::ops::RangeInclusive<char>) -> String {
+    let letters = letters.collect::<Vec<_>>();
+    loop {
+        let name = (0..2).map(|_| *rng.pick(&letters)).collect::<String>();
+        if !names.contains(&name) {
+            return name;
+        }
+    }
+}
+
+impl Generator for Day12 {
+    /// Number of small caves besides `start` and `end`. Part 2's path count
+    /// grows very quickly with it.
+    const DEFAULT_SIZE: usize = 7;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        // small caves are tracked in a 64 bit set
+        let size = size.clamp(1, 60);
+        let mut names = vec![String::from("start"), String::from("end")];
+        for _ in 0..size {
+            let name = name(rng, &names, 'a'..='z');
+            names.push(name);
+        }
+        let small = names.len();
+        for _ in 0..size / 3 + 1 {
+            let name = name(rng, &names, 'A'..='Z');
+            names.push(name);
+        }
+
+        // two big caves next to each other would allow endless paths
+        let mut edges = vec![];
+        let mut connect = |a: usize, b: usize| {
+            if a != b && !edges.contains(&(a, b)) && !edges.contains(&(b, a)) {
+                edges.push((a, b));
+            }
+        };
+        for cave in 2..names.len() {
+            for _ in 0..rng.between(1, 2) {
+                let other = if cave < small {
+                    rng.between(2, names.len() - 1)
+                } else {
+                    rng.between(2, small - 1)
+                };
+                connect(cave, other);
+            }
+        }
+        for end in 0..2 {
+            for _ in 0..rng.between(1, 2) {
+                connect(end, rng.between(2, names.len() - 1));
+            }
+        }
+        rng.shuffle(&mut edges);
+
+        let mut adjacent = vec![vec![]; names.len()];
+        let mut input = String::new();
+        for &(a, b) in edges.iter() {
+            adjacent[a].push(b);
+            adjacent[b].push(a);
+            input += &format!("{}-{}\n", names[a], names[b]);
+        }
+
+        let caves = Caves { names, adjacent };
+        Generated {
+            input,
+            part_1: Some(caves.paths(0, 1, false, &mut HashMap::new()).into()),
+            part_2: Some(caves.paths(0, 1, true, &mut HashMap::new()).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generate::check;
+
+    #[test]
+    fn generated_answers_match() {
+        for seed in 0..10 {
+            assert_eq!(check::<Day12>(seed, 4), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use common::{
+    grammar::{self, lines, pair, verify, word},
+    trace, ParseError, Solution,
+};
+
+mod generate;
+
+pub struct Vertex {
+    pub id: String,
+    pub adjacent: Vec<String>,
+}
+
+pub struct Graph {
+    pub vertices: Vec<Vertex>,
+}
+
+impl Graph {
+    pub fn from_edge_list(edges: Vec<(String, String)>) -> Self {
+        let mut vertices = vec![];
+
+        for (from_id, to_id) in edges.iter() {
+            if vertices
+                .iter()
+                .find(|vertex: &&Vertex| &vertex.id == from_id)
+                .is_none()
+            {
+                vertices.push(Vertex {
+                    id: from_id.clone(),
+                    adjacent: vec![],
+                });
+            }
+            let from_vert = vertices.iter_mut().find(|v| &v.id == from_id).unwrap();
+            if !from_vert.adjacent.contains(to_id) {
+                from_vert.adjacent.push(to_id.clone());
+            }
+
+            if vertices.iter().find(|v| &v.id == to_id).is_none() {
+                vertices.push(Vertex {
+                    id: to_id.clone(),
+                    adjacent: vec![],
+                });
+            }
+            let to_vert = vertices.iter_mut().find(|v| &v.id == to_id).unwrap();
+            if !to_vert.adjacent.contains(from_id) {
+                to_vert.adjacent.push(from_id.clone());
+            }
+        }
+
+        Self { vertices }
+    }
+
+    pub fn possible_paths<'a>(
+        &'a self,
+        start: &'a str,
+        end: &'a str,
+        path: Vec<&'a str>,
+        qualify_fn: fn(&str, Vec<&str>) -> bool,
+    ) -> Vec<Vec<&'a str>> {
+        if start == end {
+            let path = [path, vec![end]].concat();
+            trace!(Debug, "paths", "found {}", path.join(","));
+            return vec![path];
+        }
+        let start_vert = self
+            .vertices
+            .iter()
+            .find(|v| v.id == start)
+            .expect("Cannot find start vertex");
+
+        start_vert
+            .adjacent
+            .iter()
+            .flat_map(|adj| {
+                if qualify_fn(start, path.clone()) {
+                    self.possible_paths(adj, end, [path.clone(), vec![start]].concat(), qualify_fn)
+                } else {
+                    vec![]
+                }
+            })
+            .collect::<Vec<_>>()
+    }
+}
+
+pub fn is_large(id: &str) -> bool {
+    id.chars().all(|c| c.is_uppercase())
+}
+
+pub fn only_once(curr: &str, path: Vec<&str>) -> bool {
+    is_large(curr) || !path.contains(&curr)
+}
+
+fn count_paths(paths: Vec<Vec<&str>>) -> usize {
+    trace!(Info, "paths", "{} paths from start to end", paths.len());
+    paths.len()
+}
+
+pub fn part_1(graph: &Graph) -> usize {
+    count_paths(graph.possible_paths("start", "end", vec![], only_once))
+}
+
+pub fn part_2(graph: &Graph) -> usize {
+    let paths = graph.possible_paths("start", "end", vec![], |curr, path| {
+        // if path contains start/end and we encounter duplicate of it - reject
+        if path.contains(&"start") && curr == "start" || path.contains(&"end") && curr == "end" {
+            return false;
+        }
+
+        let contains_duplicate = path.iter().filter(|v| !is_large(v)).any(|v| {
+            let dups = path.iter().filter(|a| a == &v).collect::<Vec<_>>();
+            dups.len() > 1
+        });
+
+        !contains_duplicate || only_once(curr, path)
+    });
+    count_paths(paths)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    const EXAMPLE: &'static str = include_str!("../example");
+
+    type Model = Graph;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Model, ParseError> {
+        let edge = pair("-", "`from-to` edge", word("cave name"), word("cave name"));
+        let has = |cave: &'static str| {
+            move |edges: &Vec<(String, String)>| {
+                edges.iter().any(|(from, to)| from == cave || to == cave)
+            }
+        };
+        let edges = verify(lines(edge), "edges from `start`", has("start"));
+        let edges = verify(edges, "edges to `end`", has("end"));
+        let edges = grammar::parse(input, edges)?;
+        Ok(Graph::from_edge_list(edges))
+    }
+
+    fn part_1(graph: &Self::Model) -> Self::Part1 {
+        part_1(graph)
+    }
+
+    fn part_2(graph: &Self::Model) -> Self::Part2 {
+        part_2(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let graph = Day12::parse(Day12::EXAMPLE).unwrap();
+        assert_eq!(part_1(&graph), 10);
+        assert_eq!(part_2(&graph), 36);
+    }
+
+    #[test]
+    fn needs_start_and_end() {
+        let err = Day12::parse("a-b\nb-end\n").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert_eq!(err.expected, "edges from `start`");
+        let err = Day12::parse("start-b\nb-c\n").err().unwrap();
+        assert_eq!(err.expected, "edges to `end`");
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::BTreeSet;
+
+use common::generate::{Generated, Generator, Rng};
+
+use crate::Day13;
+
+type Dots = BTreeSet<(usize, usize)>;
+
+/// Same layout as `Display for Paper`.
+fn render(dots: &Dots) -> String {
+    let max_x = dots.iter().map(|&(x, _)| x).max().unwrap();
+    let max_y = dots.iter().map(|&(_, y)| y).max().unwrap();
+    let mut art = String::new();
+    for y in 0..=max_y {
+        for x in 0..=max_x {
+            art.push(if dots.contains(&(x, y)) { '█' } else { ' ' });
+        }
+        art.push('\n');
+    }
+    art
+}
+
+/// Undoes a fold along `line`: every dot ends up on one side of it or both.
+fn unfold(rng: &mut Rng, dots: &Dots, line: usize, horizontal: bool) -> Dots {
+    let mut unfolded = Dots::new();
+    for &(x, y) in dots.iter() {
+        let mirrored = if horizontal {
+            (x, 2 * line - y)
+        } else {
+            (2 * line - x, y)
+        };
+        match rng.below(3) {
+            0 => unfolded.insert((x, y)),
+            1 => unfolded.insert(mirrored),
+            _ => unfolded.insert((x, y)) | unfolded.insert(mirrored),
+        };
+    }
+    unfolded
+}
+
+impl Generator for Day13 {
+    /// Number of fold instructions.
+    const DEFAULT_SIZE: usize = 12;
+
+    /// Starts from the folded code and unfolds it, so both the code and the
+    /// dots left after the first fold are known.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let (mut width, mut height) = (rng.between(5, 40), rng.between(3, 6));
+        let mut dots = Dots::new();
+        for y in 0..height {
+            for x in 0..width {
+                if rng.chance(2, 5) {
+                    dots.insert((x, y));
+                }
+            }
+        }
+        // pin the corner so the rendered code has a known size
+        dots.insert((width - 1, height - 1));
+        let code = render(&dots);
+
+        let mut instructions = vec![];
+        let mut after_first_fold = dots.len();
+        // every fold roughly doubles the paper
+        for _ in 0..size.clamp(1, 20) {
+            after_first_fold = dots.len();
+            let horizontal = rng.chance(1, 2);
+            let size = if horizontal { &mut height } else { &mut width };
+            let line = *size + rng.below(2);
+            *size = 2 * line + 1;
+            dots = unfold(rng, &dots, line, horizontal);
+            instructions.push(format!(
+                "fold along {}={}\n",
+                if horizontal { "y" } else { "x" },
+                line
+            ));
+        }
+
+        let mut dots = dots.into_iter().collect::<Vec<_>>();
+        rng.shuffle(&mut dots);
+        let mut input = dots
+            .iter()
+            .map(|(x, y)| format!("{},{}\n", x, y))
+            .collect::<String>();
+        input += "\n";
+        input.extend(instructions.into_iter().rev());
+
+        Generated {
+            input,
+            part_1: Some(after_first_fold.into()),
+            part_2: Some(code.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generate::check;
+
+    #[test]
+    fn generated_answers_match() {
+        for seed in 0..10 {
+            assert_eq!(check::<Day13>(seed, 6), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use common::{
+    grammar::{self, keyword, lines, map, number, pair, preceded, two_sections, verify},
+    trace, trace_enabled, ParseError, Solution, Span,
+};
+use std::{collections::HashSet, fmt::Display, iter::FromIterator};
+
+mod generate;
+pub mod visual;
+
+#[derive(Clone, Copy)]
+pub enum Instruction {
+    FoldUp(usize),
+    FoldLeft(usize),
+}
+
+impl Instruction {
+    /// `fold along <axis>=<value>`
+    fn parse(source: Span) -> Result<Self, ParseError> {
+        type Fold = fn(usize) -> Instruction;
+        const AXES: &[(&str, Fold)] = &[("x", Instruction::FoldLeft), ("y", Instruction::FoldUp)];
+        let fold = preceded(
+            "fold along ",
+            "`fold along <axis>=<value>`",
+            pair(
+                "=",
+                "`<axis>=<value>`",
+                keyword("`x` or `y` axis", AXES),
+                number("fold position"),
+            ),
+        );
+        map(fold, |(fold, value)| fold(value))(source)
+    }
+
+    pub fn execute(&self, paper: &mut Paper) {
+        // counting dots is as slow as folding, so only do it when asked to
+        let before = trace_enabled!(Info, "fold").then(|| paper.dots_count());
+        match self {
+            Instruction::FoldUp(y) => paper.fold_up(*y),
+            Instruction::FoldLeft(x) => paper.fold_left(*x),
+        }
+        if let Some(before) = before {
+            let after = paper.dots_count();
+            trace!(
+                Info,
+                "fold",
+                "fold {} merged {} dots, {} left",
+                self,
+                before - after,
+                after
+            );
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::FoldUp(y) => write!(f, "y={}", y),
+            Instruction::FoldLeft(x) => write!(f, "x={}", x),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Paper {
+    pub dots: Vec<(isize, isize)>,
+}
+
+impl Paper {
+    /// One `x,y` dot per line, at least one of them.
+    fn parse(input: Span) -> Result<Self, ParseError> {
+        let dot = pair(
+            ",",
+            "`x,y` dot",
+            number::<usize>("dot x coord"),
+            number::<usize>("dot y coord"),
+        );
+        let dot = map(dot, |(x, y)| (x as isize, y as isize));
+        let dots = verify(lines(dot), "at least one dot", |dots| !dots.is_empty());
+        map(dots, |dots| Self { dots })(input)
+    }
+
+    pub fn fold_up(&mut self, fold_y: usize) {
+        let fold_y = fold_y as isize;
+        for (_, dot_y) in self.dots.iter_mut() {
+            if *dot_y > fold_y {
+                let diff = *dot_y - fold_y;
+                *dot_y = fold_y - diff;
+            }
+        }
+    }
+
+    pub fn fold_left(&mut self, fold_x: usize) {
+        let fold_x = fold_x as isize;
+        for (dot_x, _) in self.dots.iter_mut() {
+            if *dot_x > fold_x {
+                let diff = *dot_x - fold_x;
+                *dot_x = fold_x - diff;
+            }
+        }
+    }
+
+    pub fn dots_count(&self) -> usize {
+        let set: HashSet<&(isize, isize)> = HashSet::from_iter(self.dots.iter());
+        set.len()
+    }
+}
+
+impl Display for Paper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let max_x = self.dots.iter().map(|(x, _)| x).max().unwrap();
+        let max_y = self.dots.iter().map(|(_, y)| y).max().unwrap();
+        for y in 0..=*max_y {
+            for x in 0..=*max_x {
+                match self.dots.iter().find(|&&dot| dot == (x, y)) {
+                    Some(_) => write!(f, "█")?,
+                    None => write!(f, " ")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn part_1(paper: &Paper, instructions: &[Instruction]) -> usize {
+    let first_instruction = instructions[0];
+    let mut paper = paper.clone();
+    first_instruction.execute(&mut paper);
+    paper.dots_count()
+}
+
+pub fn part_2(paper: &Paper, instructions: &[Instruction]) -> String {
+    let mut paper = paper.clone();
+    for instruction in instructions.iter() {
+        instruction.execute(&mut paper);
+    }
+
+    paper.to_string()
+}
+
+pub struct Input {
+    pub paper: Paper,
+    pub instructions: Vec<Instruction>,
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    const EXAMPLE: &'static str = include_str!("../example");
+
+    type Model = Input;
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Model, ParseError> {
+        let folds = verify(
+            lines(Instruction::parse),
+            "at least one fold instruction",
+            |folds| !folds.is_empty(),
+        );
+        let input_grammar =
+            two_sections("blank line before fold instructions", Paper::parse, folds);
+        let (paper, instructions) = grammar::parse(input, input_grammar)?;
+        Ok(Input {
+            paper,
+            instructions,
+        })
+    }
+
+    fn part_1(input: &Self::Model) -> Self::Part1 {
+        part_1(&input.paper, &input.instructions)
+    }
+
+    fn part_2(input: &Self::Model) -> Self::Part2 {
+        part_2(&input.paper, &input.instructions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::property;
+
+    type Sheet = Vec<Vec<bool>>;
+
+    /// Folds a sheet of paper for real, or `None` if a dot lies on the fold.
+    fn naive_fold(sheet: &Sheet, instruction: Instruction) -> Option<Sheet> {
+        let transposed = |sheet: &Sheet| -> Sheet {
+            (0..sheet[0].len())
+                .map(|x| sheet.iter().map(|row| row[x]).collect())
+                .collect()
+        };
+        match instruction {
+            Instruction::FoldLeft(x) => {
+                let folded = naive_fold(&transposed(sheet), Instruction::FoldUp(x))?;
+                Some(transposed(&folded))
+            }
+            Instruction::FoldUp(y) => {
+                if sheet[y].contains(&true) {
+                    return None;
+                }
+                let mut folded = sheet[..y].to_vec();
+                for (below, row) in sheet[y + 1..].iter().enumerate() {
+                    for (x, &dot) in row.iter().enumerate() {
+                        folded[y - 1 - below][x] |= dot;
+                    }
+                }
+                Some(folded)
+            }
+        }
+    }
+
+    fn naive_render(sheet: &Sheet) -> String {
+        let dots = |row: &Vec<bool>| row.iter().rposition(|&dot| dot);
+        let width = sheet.iter().filter_map(dots).max().unwrap() + 1;
+        let height = sheet.iter().rposition(|row| dots(row).is_some()).unwrap() + 1;
+        sheet[..height]
+            .iter()
+            .map(|row| {
+                let row = row[..width].iter();
+                row.map(|&dot| if dot { '█' } else { ' ' })
+                    .collect::<String>()
+                    + "\n"
+            })
+            .collect()
+    }
+
+    #[test]
+    fn example() {
+        let input = Day13::parse(Day13::EXAMPLE).unwrap();
+        assert_eq!(part_1(&input.paper, &input.instructions), 17);
+        assert_eq!(
+            part_2(&input.paper, &input.instructions),
+            "█████\n█   █\n█   █\n█   █\n█████\n"
+        );
+    }
+
+    #[test]
+    fn reports_bad_fold() {
+        let input = "1,2\n\nfold along x=1\nfold along z=3\n";
+        let err = Day13::parse(input).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 12: expected `x` or `y` axis, found \"z\""
+        );
+    }
+
+    #[test]
+    fn rejects_paper_without_dots() {
+        let err = Day13::parse("\n\nfold along x=1\n").err().unwrap();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 1, "at least one dot")
+        );
+    }
+
+    #[test]
+    fn matches_naive_folding() {
+        property::check(200, |rng| {
+            let (mut width, mut height) = (rng.between(3, 30), rng.between(3, 30));
+            let mut sheet = vec![vec![false; width]; height];
+            let mut input = String::new();
+            for _ in 0..rng.between(1, 40) {
+                let (x, y) = (rng.below(width), rng.below(height));
+                sheet[y][x] = true;
+                input += &format!("{},{}\n", x, y);
+            }
+
+            input += "\n";
+            let mut instructions = vec![];
+            let mut folded = vec![];
+            loop {
+                let horizontal = rng.chance(1, 2);
+                let size = if horizontal { height } else { width };
+                // never fold past the edge of the paper or through a dot
+                let lines = ((size / 2).max(1)..size)
+                    .map(|line| match horizontal {
+                        true => Instruction::FoldUp(line),
+                        false => Instruction::FoldLeft(line),
+                    })
+                    .filter_map(|instruction| Some((instruction, naive_fold(&sheet, instruction)?)))
+                    .collect::<Vec<_>>();
+                if lines.is_empty() || !instructions.is_empty() && rng.chance(1, 3) {
+                    break;
+                }
+                let (instruction, next) = rng.pick(&lines).clone();
+                match instruction {
+                    Instruction::FoldUp(y) => {
+                        height = y;
+                        input += &format!("fold along y={}\n", y);
+                    }
+                    Instruction::FoldLeft(x) => {
+                        width = x;
+                        input += &format!("fold along x={}\n", x);
+                    }
+                }
+                instructions.push(instruction);
+                sheet = next;
+                folded.push(sheet.concat().iter().filter(|&&dot| dot).count());
+            }
+            if instructions.is_empty() {
+                return;
+            }
+
+            let input = Day13::parse(&input).unwrap();
+            assert_eq!(part_1(&input.paper, &input.instructions), folded[0]);
+            assert_eq!(
+                part_2(&input.paper, &input.instructions),
+                naive_render(&sheet)
+            );
+        });
+    }
+}
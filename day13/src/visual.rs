@@ -0,0 +1,103 @@
+use grid::{
+    image::Rgb,
+    visual::{Cell, Scene, Visual},
+    Grid,
+};
+
+use crate::{Day13, Input, Instruction};
+
+/// Applies the fold instructions one per frame, marking the next fold line
+/// and the dots about to be folded over.
+pub struct Folds {
+    input: Input,
+    folded: usize,
+}
+
+impl Folds {
+    pub fn new(input: Input) -> Self {
+        Folds { input, folded: 0 }
+    }
+}
+
+impl Scene for Folds {
+    fn frame(&self) -> Grid<Cell> {
+        let next = self.input.instructions.get(self.folded);
+        let dots = &self.input.paper.dots;
+        let width = dots.iter().map(|&(x, _)| x as usize + 1).max().unwrap_or(0);
+        let height = dots.iter().map(|&(_, y)| y as usize + 1).max().unwrap_or(0);
+
+        let mut frame = Grid::from_fn(width, height, |(x, y)| match next {
+            Some(Instruction::FoldLeft(fold)) if x == *fold => Cell::new('│', 196),
+            Some(Instruction::FoldUp(fold)) if y == *fold => Cell::new('─', 196),
+            _ => Cell::EMPTY,
+        });
+        for &(x, y) in dots.iter() {
+            let moving = match next {
+                Some(Instruction::FoldLeft(fold)) => x as usize > *fold,
+                Some(Instruction::FoldUp(fold)) => y as usize > *fold,
+                None => false,
+            };
+            frame[(x as usize, y as usize)] = Cell::new('█', if moving { 208 } else { 51 });
+        }
+        frame
+    }
+
+    fn advance(&mut self) -> bool {
+        match self.input.instructions.get(self.folded) {
+            Some(instruction) => {
+                instruction.execute(&mut self.input.paper);
+                self.folded += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn status(&self) -> String {
+        let next = match self.input.instructions.get(self.folded) {
+            Some(Instruction::FoldLeft(x)) => format!("next fold x={}", x),
+            Some(Instruction::FoldUp(y)) => format!("next fold y={}", y),
+            None => String::from("all folded"),
+        };
+        format!(
+            "fold {}/{}, {} dots, {}",
+            self.folded,
+            self.input.instructions.len(),
+            self.input.paper.dots_count(),
+            next
+        )
+    }
+}
+
+impl Visual for Day13 {
+    type Scene = Folds;
+
+    fn scene(input: Self::Model) -> Self::Scene {
+        Folds::new(input)
+    }
+
+    /// The dots left once every fold is done.
+    fn image(input: &Self::Model) -> Grid<Rgb> {
+        let mut paper = input.paper.clone();
+        for instruction in input.instructions.iter() {
+            instruction.execute(&mut paper);
+        }
+        let width = paper
+            .dots
+            .iter()
+            .map(|&(x, _)| x as usize + 1)
+            .max()
+            .unwrap_or(0);
+        let height = paper
+            .dots
+            .iter()
+            .map(|&(_, y)| y as usize + 1)
+            .max()
+            .unwrap_or(0);
+        let mut image = Grid::filled(width, height, Rgb::BLACK);
+        for &(x, y) in paper.dots.iter() {
+            image[(x as usize, y as usize)] = Rgb::WHITE;
+        }
+        image
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use common::generate::{Generated, Generator, Rng};
+
+use crate::Day14;
+
+type Counts = HashMap<char, usize>;
+
+fn spread(counts: &Counts) -> usize {
+    counts.values().max().unwrap() - counts.values().min().unwrap()
+}
+
+/// Builds the polymer for real; fine for a handful of steps.
+fn grow(template: &[char], rules: &HashMap<(char, char), char>, steps: usize) -> Counts {
+    let mut polymer = template.to_vec();
+    for _ in 0..steps {
+        let mut next = vec![polymer[0]];
+        for pair in polymer.windows(2) {
+            next.push(rules[&(pair[0], pair[1])]);
+            next.push(pair[1]);
+        }
+        polymer = next;
+    }
+    let mut counts = Counts::new();
+    for c in polymer {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Elements inserted between `a` and `b` over `steps` steps.
+fn inserted(
+    pair: (char, char),
+    steps: usize,
+    rules: &HashMap<(char, char), char>,
+    memo: &mut HashMap<((char, char), usize), Counts>,
+) -> Counts {
+    if steps == 0 {
+        return Counts::new();
+    }
+    if let Some(counts) = memo.get(&(pair, steps)) {
+        return counts.clone();
+    }
+    let middle = rules[&pair];
+    let mut counts = inserted((pair.0, middle), steps - 1, rules, memo);
+    for (c, n) in inserted((middle, pair.1), steps - 1, rules, memo) {
+        *counts.entry(c).or_insert(0) += n;
+    }
+    *counts.entry(middle).or_insert(0) += 1;
+    memo.insert((pair, steps), counts.clone());
+    counts
+}
+
+impl Generator for Day14 {
+    /// Length of the polymer template.
+    const DEFAULT_SIZE: usize = 20;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut alphabet = ('A'..='Z').collect::<Vec<_>>();
+        rng.shuffle(&mut alphabet);
+        alphabet.truncate(rng.between(2, 10));
+
+        let template = (0..size.max(2))
+            .map(|_| *rng.pick(&alphabet))
+            .collect::<Vec<_>>();
+
+        // a rule for every pair, like the real input
+        let mut input = template.iter().collect::<String>() + "\n\n";
+        let mut rules = HashMap::new();
+        for &a in alphabet.iter() {
+            for &b in alphabet.iter() {
+                let insert = *rng.pick(&alphabet);
+                rules.insert((a, b), insert);
+                input += &format!("{}{} -> {}\n", a, b, insert);
+            }
+        }
+
+        let mut memo = HashMap::new();
+        let mut counts = grow(&template, &rules, 0);
+        for pair in template.windows(2) {
+            for (c, n) in inserted((pair[0], pair[1]), 40, &rules, &mut memo) {
+                *counts.entry(c).or_insert(0) += n;
+            }
+        }
+
+        Generated {
+            input,
+            part_1: Some(spread(&grow(&template, &rules, 10)).into()),
+            part_2: Some(spread(&counts).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generate::check;
+
+    #[test]
+    fn generated_answers_match() {
+        for seed in 0..10 {
+            assert_eq!(check::<Day14>(seed, 10), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,394 @@
+use common::{
+    arith::{Arithmetic, BigUint, CheckedCount, Count, Overflow},
+    grammar::{self, lines, map, pair, single, two_sections, verify, word},
+    snapshot::{Simulate, Snapshot},
+    trace, Answer, ParseError, Simulation, Solution, Span,
+};
+use std::{collections::HashMap, iter};
+
+mod generate;
+
+#[derive(Clone, Copy)]
+pub struct Rule {
+    pub pair: (char, char),
+    pub insert: char,
+}
+
+impl Rule {
+    /// `AB -> C`
+    fn parse(value: Span) -> Result<Self, ParseError> {
+        let elements = verify(
+            word("pair of two elements"),
+            "pair of two elements",
+            |pair| pair.chars().count() == 2,
+        );
+        let elements = map(elements, |pair| {
+            let mut elements = pair.chars();
+            (elements.next().unwrap(), elements.next().unwrap())
+        });
+        let rule = pair(" -> ", "`AB -> C` rule", elements, single("single element"));
+        map(rule, |(pair, insert)| Self { pair, insert })(value)
+    }
+}
+
+#[derive(Hash, PartialEq, Eq, Clone, Copy)]
+pub struct PolymerLink(pub char, pub Option<char>);
+
+impl From<Rule> for PolymerLink {
+    fn from(rule: Rule) -> Self {
+        Self(rule.pair.0, Some(rule.pair.1))
+    }
+}
+
+/// Pairs of adjacent elements in a polymer, counted with `C`.
+#[derive(Clone)]
+pub struct Polymer<C = usize> {
+    map: HashMap<PolymerLink, C>,
+    rules: Vec<Rule>,
+}
+
+impl<C: Count> Polymer<C> {
+    pub fn from_template(template: &str, rules: Vec<Rule>) -> Self {
+        let mut map = HashMap::new();
+        let chars = template.chars();
+        let chars_offset = template.chars().skip(1).map(Some).chain(iter::repeat(None));
+        for (a, b) in chars.zip(chars_offset) {
+            map.entry(PolymerLink(a, b))
+                .or_insert_with(|| C::from_usize(0))
+                .increase(&C::from_usize(1));
+        }
+        Self { map, rules }
+    }
+
+    pub fn get_count(&self) -> HashMap<char, C> {
+        let mut map = HashMap::new();
+        for (c, count) in self.map.iter() {
+            map.entry(c.0)
+                .or_insert_with(|| C::from_usize(0))
+                .increase(count);
+        }
+        map
+    }
+
+    /// How many more of the most common element there are than of the least
+    /// common one.
+    pub fn spread(&self) -> C {
+        let count = self.get_count();
+        let (most_common, most) = count.iter().max_by_key(|(_, count)| *count).unwrap();
+        let (least_common, least) = count.iter().min_by_key(|(_, count)| *count).unwrap();
+        trace!(
+            Info,
+            "polymer",
+            "{} is most common with {}, {} least common with {}",
+            most_common,
+            most,
+            least_common,
+            least
+        );
+        most.difference(least)
+    }
+
+    /// The first pair count that overflowed, if any did.
+    pub fn overflow(&self) -> Option<&Overflow> {
+        self.map.values().find_map(|count| count.overflow())
+    }
+}
+
+impl Polymer {
+    /// The same polymer, counted with `D` from now on.
+    pub fn recount<D: Count>(&self) -> Polymer<D> {
+        Polymer {
+            map: self
+                .map
+                .iter()
+                .map(|(&link, &count)| (link, D::from_usize(count)))
+                .collect(),
+            rules: self.rules.clone(),
+        }
+    }
+}
+
+impl<C: Count> Simulation for Polymer<C> {
+    fn step(&mut self) {
+        let mut new_map = HashMap::new();
+        let mut add = |link, count: &C| {
+            new_map
+                .entry(link)
+                .or_insert_with(|| C::from_usize(0))
+                .increase(count)
+        };
+        for rule in self.rules.iter() {
+            let matching_link = (*rule).into();
+            if let Some(matched_count) = self.map.get(&matching_link) {
+                add(
+                    PolymerLink(matching_link.0, Some(rule.insert)),
+                    matched_count,
+                );
+                add(PolymerLink(rule.insert, matching_link.1), matched_count);
+            }
+        }
+
+        for (k, v) in self.map.iter() {
+            if self
+                .rules
+                .iter()
+                .find(|&r| PolymerLink::from(*r) == *k)
+                .is_none()
+            {
+                add(*k, v);
+            }
+        }
+
+        self.map = new_map;
+    }
+}
+
+/// Counted with `BigUint`, since pair counts outgrow a `usize` by step 65.
+impl Snapshot for Polymer<BigUint> {
+    /// `pair <ab> <count>` lines in order, where the last element of the
+    /// polymer shows up as a pair of one, followed by the rules as given.
+    fn save(&self, out: &mut String) {
+        let mut pairs = self.map.iter().collect::<Vec<_>>();
+        pairs.sort_by_key(|(link, _)| (link.0, link.1));
+        for (PolymerLink(a, b), count) in pairs {
+            let b = b.map(String::from).unwrap_or_default();
+            out.push_str(&format!("pair {}{} {}\n", a, b, count));
+        }
+        for rule in self.rules.iter() {
+            let (a, b) = rule.pair;
+            out.push_str(&format!("rule {}{} -> {}\n", a, b, rule.insert));
+        }
+    }
+
+    fn restore(input: Span) -> Result<Self, ParseError> {
+        let mut map = HashMap::new();
+        let mut rules = vec![];
+        for line in input.lines() {
+            if let Some(rule) = line.strip_prefix("rule ") {
+                rules.push(Rule::parse(rule)?);
+                continue;
+            }
+            let (pair, count) = line
+                .strip_prefix("pair ")
+                .and_then(|pair| pair.split_once(" "))
+                .ok_or_else(|| line.error("`pair <ab> <count>` or `rule <ab> -> <c>` line"))?;
+            let mut it = pair.chars().map(|(c, _)| c);
+            let link = match (it.next(), it.next(), it.next()) {
+                (Some(a), b, None) => PolymerLink(a, b),
+                _ => return Err(pair.error("pair of one or two elements")),
+            };
+            map.entry(link)
+                .or_insert_with(BigUint::default)
+                .increase(&count.parse("pair count")?);
+        }
+        Ok(Self { map, rules })
+    }
+}
+
+fn spread_after_steps(polymer: &Polymer, steps: usize) -> usize {
+    let mut polymer = polymer.clone();
+    for step in 1..=steps {
+        polymer.step();
+        trace!(
+            Debug,
+            "polymer",
+            "step {}: {} kinds of pairs",
+            step,
+            polymer.map.len()
+        );
+    }
+    polymer.spread()
+}
+
+pub fn part_1(polymer: &Polymer) -> usize {
+    spread_after_steps(polymer, 10)
+}
+
+pub fn part_2(polymer: &Polymer) -> usize {
+    spread_after_steps(polymer, 40)
+}
+
+/// The spread after `steps` steps, stopping at the first step on which a
+/// pair count no longer fits in `C`.
+pub fn spread_after<C: Count>(polymer: &Polymer, steps: usize) -> Result<Answer, Overflow> {
+    let mut polymer = polymer.recount::<C>();
+    for step in 1..=steps {
+        polymer.step();
+        if let Some(overflow) = polymer.overflow() {
+            return Err(overflow.clone().at(format!("step {}", step)));
+        }
+    }
+    polymer.spread().answer()
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    const EXAMPLE: &'static str = include_str!("../example");
+
+    type Model = Polymer;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Model, ParseError> {
+        let template = verify(
+            word("polymer template"),
+            "single line template",
+            |template| !template.contains('\n'),
+        );
+        let input_grammar =
+            two_sections("blank line before the rules", template, lines(Rule::parse));
+        let (template, rules) = grammar::parse(input, input_grammar)?;
+        Ok(Polymer::from_template(&template, rules))
+    }
+
+    fn part_1(polymer: &Self::Model) -> Self::Part1 {
+        part_1(polymer)
+    }
+
+    fn part_2(polymer: &Self::Model) -> Self::Part2 {
+        part_2(polymer)
+    }
+
+    fn solve(polymer: &Self::Model, part: u8, arithmetic: Arithmetic) -> Result<Answer, Overflow> {
+        let steps = if part == 1 { 10 } else { 40 };
+        match arithmetic {
+            Arithmetic::Native => spread_after::<usize>(polymer, steps),
+            Arithmetic::Checked => spread_after::<CheckedCount>(polymer, steps),
+            Arithmetic::Big => spread_after::<BigUint>(polymer, steps),
+        }
+    }
+}
+
+impl Simulate for Day14 {
+    type State = Polymer<BigUint>;
+
+    fn start(polymer: &Self::Model) -> Self::State {
+        polymer.recount()
+    }
+
+    fn summary(polymer: &Self::State) -> Answer {
+        polymer.spread().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{property, snapshot};
+
+    /// Builds the whole polymer string, step by step.
+    fn naive_step(polymer: &str, rules: &HashMap<(char, char), char>) -> String {
+        let chars = polymer.chars().collect::<Vec<_>>();
+        let mut next = String::new();
+        for pair in chars.windows(2) {
+            next.push(pair[0]);
+            if let Some(&insert) = rules.get(&(pair[0], pair[1])) {
+                next.push(insert);
+            }
+        }
+        next.extend(chars.last());
+        next
+    }
+
+    #[test]
+    fn example() {
+        let polymer = Day14::parse(Day14::EXAMPLE).unwrap();
+        assert_eq!(part_1(&polymer), 1588);
+        assert_eq!(part_2(&polymer), 2188189693529);
+    }
+
+    #[test]
+    fn reports_bad_rule() {
+        let input = "NNCB\n\nCH -> B\nCH => B\n";
+        let err = Day14::parse(input).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 1: expected `AB -> C` rule, found \"CH => B\""
+        );
+    }
+
+    #[test]
+    fn big_counts_outgrow_checked_ones() {
+        let polymer = Day14::parse(Day14::EXAMPLE).unwrap();
+        assert_eq!(
+            spread_after::<CheckedCount>(&polymer, 40),
+            Ok(Answer::Number(2188189693529))
+        );
+        let overflow = spread_after::<CheckedCount>(&polymer, 100).unwrap_err();
+        assert_eq!(overflow.context.as_deref(), Some("step 65"));
+        assert_eq!(
+            spread_after::<BigUint>(&polymer, 100),
+            Ok(Answer::Number(2535296262066596202993060773164))
+        );
+    }
+
+    #[test]
+    fn snapshot_resumes_where_it_stopped() {
+        let input = Day14::parse(Day14::EXAMPLE).unwrap();
+        let mut polymer = Day14::start(&input);
+        polymer.run(25);
+        let text = snapshot::write::<Day14>(25, &polymer);
+        let (step, mut resumed) = snapshot::read::<Day14>(&text).unwrap();
+        assert_eq!(step, 25);
+        assert_eq!(snapshot::write::<Day14>(step, &resumed), text);
+        resumed.run(15);
+        assert_eq!(resumed.spread(), BigUint::from_usize(2188189693529));
+        resumed.run(40);
+        assert_eq!(
+            Day14::summary(&resumed),
+            spread_after::<BigUint>(&input, 80).unwrap()
+        );
+    }
+
+    #[test]
+    fn step_keeps_unmatched_pairs() {
+        // NNB -> NBNB: NB is both produced by the rule and carried over
+        let mut polymer = Day14::parse("NNB\n\nNN -> B\n").unwrap();
+        polymer.step();
+
+        assert_eq!(polymer.map.get(&PolymerLink('N', Some('B'))), Some(&2));
+        assert_eq!(polymer.map.get(&PolymerLink('B', Some('N'))), Some(&1));
+        assert_eq!(polymer.map.get(&PolymerLink('B', None)), Some(&1));
+        assert_eq!(polymer.map.get(&PolymerLink('N', Some('N'))), None);
+
+        let count = polymer.get_count();
+        assert_eq!(count.get(&'N'), Some(&2));
+        assert_eq!(count.get(&'B'), Some(&2));
+    }
+
+    #[test]
+    fn matches_naive_simulation() {
+        property::check(200, |rng| {
+            let elements = ['B', 'C', 'H', 'N'][..rng.between(1, 4)].to_vec();
+            let mut template = (0..rng.between(1, 8))
+                .map(|_| *rng.pick(&elements))
+                .collect::<String>();
+
+            // leave some pairs without a rule
+            let mut input = format!("{}\n\n", template);
+            let mut rules = HashMap::new();
+            for &a in elements.iter() {
+                for &b in elements.iter() {
+                    if rng.chance(3, 4) {
+                        let insert = *rng.pick(&elements);
+                        rules.insert((a, b), insert);
+                        input += &format!("{}{} -> {}\n", a, b, insert);
+                    }
+                }
+            }
+
+            let mut polymer = Day14::parse(&input).unwrap();
+            for _ in 0..rng.between(0, 10) {
+                template = naive_step(&template, &rules);
+                polymer.step();
+            }
+            let mut counts = HashMap::new();
+            for c in template.chars() {
+                *counts.entry(c).or_insert(0) += 1;
+            }
+            assert_eq!(polymer.get_count(), counts);
+        });
+    }
+}
@@ -0,0 +1,53 @@
+use common::generate::{Generated, Generator, Rng};
+
+use crate::Day2;
+
+impl Generator for Day2 {
+    /// Number of commands.
+    const DEFAULT_SIZE: usize = 1000;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut input = String::new();
+        let mut position = 0;
+        // part 1's depth and part 2's aim move together
+        let mut aim = 0;
+        let mut depth = 0;
+        for _ in 0..size {
+            let value = rng.between(1, 9);
+            match rng.below(3) {
+                0 => {
+                    input += &format!("forward {}\n", value);
+                    position += value;
+                    depth += aim * value;
+                }
+                1 if aim >= value => {
+                    input += &format!("up {}\n", value);
+                    aim -= value;
+                }
+                _ => {
+                    input += &format!("down {}\n", value);
+                    aim += value;
+                }
+            }
+        }
+
+        Generated {
+            input,
+            part_1: Some((position * aim).into()),
+            part_2: Some((position * depth).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generate::check;
+
+    #[test]
+    fn generated_answers_match() {
+        for seed in 0..10 {
+            assert_eq!(check::<Day2>(seed, 200), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use common::{
+    arith::{Arithmetic, Checked, Overflow},
+    grammar::{keyword, lines, map, number, pair, verify},
+    options::{DayOption, Options},
+    trace, Answer, ParseError, Solution, Span,
+};
+use submarine::{CourseError, Submarine, Surfacing};
+
+mod generate;
+pub mod submarine;
+
+pub enum Command {
+    Forward(i64),
+    Down(i64),
+    Up(i64),
+}
+
+type Constructor = fn(i64) -> Command;
+
+const COMMANDS: &[(&str, Constructor)] = &[
+    ("forward", Command::Forward),
+    ("down", Command::Down),
+    ("up", Command::Up),
+];
+
+/// `<command> <value>`
+pub fn parse_line(line: Span) -> Result<Command, ParseError> {
+    let value = verify(
+        number::<i64>("unsigned integer"),
+        "unsigned integer",
+        |&v| v >= 0,
+    );
+    let command = pair(
+        " ",
+        "`<command> <value>`",
+        keyword("`forward`, `down` or `up`", COMMANDS),
+        value,
+    );
+    map(command, |(command, value)| command(value))(line)
+}
+
+/// Up and down change depth directly.
+pub fn part_1(commands: &[Command], surfacing: Surfacing) -> Result<i64, CourseError> {
+    let mut submarine = Submarine::default();
+
+    for (idx, command) in commands.iter().enumerate() {
+        match *command {
+            Command::Forward(v) => submarine.forward(idx, v)?,
+            Command::Down(v) => submarine.dive(idx, v, surfacing)?,
+            Command::Up(v) => submarine.dive(idx, -v, surfacing)?,
+        }
+    }
+    trace!(
+        Info,
+        "course",
+        "ended at position {}, depth {}",
+        submarine.position,
+        submarine.depth
+    );
+    submarine.answer()
+}
+
+/// Up and down change aim, which forward turns into depth.
+pub fn part_2(commands: &[Command], surfacing: Surfacing) -> Result<i64, CourseError> {
+    let mut submarine = Submarine::default();
+
+    for (idx, command) in commands.iter().enumerate() {
+        match *command {
+            Command::Forward(v) => {
+                submarine.forward(idx, v)?;
+                let dive = Submarine::at(idx, || submarine.aim.try_mul(v))?;
+                submarine.dive(idx, dive, surfacing)?;
+            }
+            Command::Down(v) => submarine.turn(idx, v)?,
+            Command::Up(v) => submarine.turn(idx, -v)?,
+        }
+    }
+    trace!(
+        Info,
+        "course",
+        "ended at position {}, depth {}, aim {}",
+        submarine.position,
+        submarine.depth,
+        submarine.aim
+    );
+    submarine.answer()
+}
+
+/// The commands, and what to do when they take the submarine above the
+/// surface.
+pub struct Course {
+    commands: Vec<Command>,
+    surfacing: Surfacing,
+}
+
+impl Course {
+    /// With `Surfacing::Error`, the first command that surfaces in either
+    /// part is a parse error.
+    pub fn parse(input: &str, surfacing: Surfacing) -> Result<Self, ParseError> {
+        let input = Span::new(input);
+        let commands = lines(parse_line)(input)?;
+        if surfacing == Surfacing::Error {
+            let surfaced = [part_1, part_2]
+                .iter()
+                .filter_map(|part| match part(&commands, surfacing) {
+                    Err(CourseError::Surfaced { command, .. }) => Some(command),
+                    _ => None,
+                })
+                .min();
+            if let Some(command) = surfaced {
+                const EXPECTED: &str = "command that keeps the submarine below the surface";
+                let line = input
+                    .lines()
+                    .nth(command)
+                    .ok_or_else(|| input.end().error(EXPECTED))?;
+                return Err(line.error(EXPECTED));
+            }
+        }
+        Ok(Course {
+            commands,
+            surfacing,
+        })
+    }
+
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    fn solve(&self, part: u8) -> Result<i64, Overflow> {
+        let solution = match part {
+            1 => part_1(&self.commands, self.surfacing),
+            _ => part_2(&self.commands, self.surfacing),
+        };
+        solution.map_err(|err| match err {
+            CourseError::Overflow(overflow) => overflow,
+            // `parse` already turned these into errors, and nothing else can
+            // build a `Course`
+            surfaced => panic!("{}", surfaced),
+        })
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    const EXAMPLE: &'static str = include_str!("../example");
+
+    type Model = Course;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    /// `--surfacing clamp|negative|error`, for a submarine heading above the
+    /// surface.
+    const OPTIONS: &'static [DayOption] = &[DayOption::of::<Surfacing>("--surfacing")];
+
+    fn parse(input: &str) -> Result<Self::Model, ParseError> {
+        Course::parse(input, Surfacing::default())
+    }
+
+    fn parse_with(input: &str, options: &Options) -> Result<Self::Model, ParseError> {
+        Course::parse(input, options.get("--surfacing"))
+    }
+
+    /// Overflowing panics here; `--arith checked` reports it instead.
+    fn part_1(course: &Self::Model) -> Self::Part1 {
+        course
+            .solve(1)
+            .unwrap_or_else(|overflow| panic!("{}", overflow))
+    }
+
+    fn part_2(course: &Self::Model) -> Self::Part2 {
+        course
+            .solve(2)
+            .unwrap_or_else(|overflow| panic!("{}", overflow))
+    }
+
+    fn solve(course: &Self::Model, part: u8, _: Arithmetic) -> Result<Answer, Overflow> {
+        course.solve(part).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SURFACING: &str = "forward 5\ndown 2\nup 3\ndown 4\n";
+    const AIMED_UP: &str = "down 2\nup 3\nforward 4\ndown 5\nforward 1\n";
+
+    fn commands(input: &str) -> Vec<Command> {
+        Course::parse(input, Surfacing::Negative).unwrap().commands
+    }
+
+    #[test]
+    fn example() {
+        let commands = commands(Day2::EXAMPLE);
+        for surfacing in [Surfacing::Clamp, Surfacing::Negative, Surfacing::Error] {
+            assert_eq!(part_1(&commands, surfacing), Ok(150));
+            assert_eq!(part_2(&commands, surfacing), Ok(900));
+        }
+    }
+
+    #[test]
+    fn clamp_stops_at_the_surface() {
+        assert_eq!(part_1(&commands(SURFACING), Surfacing::Clamp), Ok(5 * 4));
+        assert_eq!(part_2(&commands(AIMED_UP), Surfacing::Clamp), Ok(5 * 4));
+    }
+
+    #[test]
+    fn negative_keeps_going_up() {
+        assert_eq!(part_1(&commands(SURFACING), Surfacing::Negative), Ok(5 * 3));
+        assert_eq!(part_2(&commands(AIMED_UP), Surfacing::Negative), Ok(0));
+        assert_eq!(
+            part_1(&commands("forward 2\nup 3\n"), Surfacing::Negative),
+            Ok(-6)
+        );
+    }
+
+    #[test]
+    fn error_names_the_command() {
+        let err = part_1(&commands(SURFACING), Surfacing::Error).unwrap_err();
+        assert_eq!(
+            err,
+            CourseError::Surfaced {
+                command: 2,
+                depth: -1
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "command 3 takes the submarine 1 above the surface"
+        );
+        let err = part_2(&commands(AIMED_UP), Surfacing::Error).unwrap_err();
+        assert_eq!(
+            err,
+            CourseError::Surfaced {
+                command: 2,
+                depth: -4
+            }
+        );
+
+        let options = Options::default().with("--surfacing", "error");
+        let err = Day2::parse_with(SURFACING, &options).err().unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (3, 1, "up 3"));
+    }
+
+    #[test]
+    fn overflow_names_the_command() {
+        let commands = commands("down 4000000000\nforward 4000000000\n");
+        let err = part_2(&commands, Surfacing::Negative).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "4000000000 * 4000000000 overflows at command 2"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use std::{fmt::Display, str::FromStr};
+
+use common::arith::{Checked, Overflow};
+
+/// What happens when a command would take the submarine above the surface.
+/// Only depth surfaces; aim is free to point upwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Surfacing {
+    /// Stop at the surface.
+    Clamp,
+    /// Keep going, to a negative depth.
+    #[default]
+    Negative,
+    /// Refuse the command.
+    Error,
+}
+
+impl FromStr for Surfacing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "clamp" => Ok(Surfacing::Clamp),
+            "negative" => Ok(Surfacing::Negative),
+            "error" => Ok(Surfacing::Error),
+            _ => Err(format!(
+                "unknown surfacing {}, expected clamp, negative or error",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CourseError {
+    /// The command at index `command` would have taken the submarine to
+    /// `depth`, above the surface.
+    Surfaced {
+        command: usize,
+        depth: i64,
+    },
+    Overflow(Overflow),
+}
+
+impl Display for CourseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CourseError::Surfaced { command, depth } => write!(
+                f,
+                "command {} takes the submarine {} above the surface",
+                command + 1,
+                -depth
+            ),
+            CourseError::Overflow(overflow) => write!(f, "{}", overflow),
+        }
+    }
+}
+
+impl std::error::Error for CourseError {}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Submarine {
+    pub position: i64,
+    pub depth: i64,
+    pub aim: i64,
+}
+
+impl Submarine {
+    /// Runs the command at index `command` through `step`, pointing any
+    /// overflow at it.
+    pub(crate) fn at<T>(
+        command: usize,
+        step: impl FnOnce() -> Result<T, Overflow>,
+    ) -> Result<T, CourseError> {
+        step().map_err(|overflow| {
+            CourseError::Overflow(overflow.at(format!("command {}", command + 1)))
+        })
+    }
+
+    pub fn forward(&mut self, command: usize, by: i64) -> Result<(), CourseError> {
+        self.position = Self::at(command, || self.position.try_add(by))?;
+        Ok(())
+    }
+
+    /// Changes depth by `change`, going no higher than `surfacing` allows.
+    pub fn dive(
+        &mut self,
+        command: usize,
+        change: i64,
+        surfacing: Surfacing,
+    ) -> Result<(), CourseError> {
+        let depth = Self::at(command, || self.depth.try_add(change))?;
+        self.depth = match surfacing {
+            Surfacing::Clamp => depth.max(0),
+            Surfacing::Error if depth < 0 => return Err(CourseError::Surfaced { command, depth }),
+            Surfacing::Negative | Surfacing::Error => depth,
+        };
+        Ok(())
+    }
+
+    pub fn turn(&mut self, command: usize, change: i64) -> Result<(), CourseError> {
+        self.aim = Self::at(command, || self.aim.try_add(change))?;
+        Ok(())
+    }
+
+    pub fn answer(&self) -> Result<i64, CourseError> {
+        self.position
+            .try_mul(self.depth)
+            .map_err(CourseError::Overflow)
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+
+use common::generate::{Generated, Generator, Rng};
+
+use crate::Day3;
+
+/// Keeps the numbers whose bit at `shift` is the one `keep` picks given the
+/// counts of zeros and ones, until a single number is left.
+fn rating(mut numbers: Vec<usize>, width: usize, keep: fn(usize, usize) -> usize) -> usize {
+    let mut shift = width;
+    while numbers.len() > 1 {
+        shift -= 1;
+        let ones = numbers.iter().filter(|&&n| n >> shift & 1 == 1).count();
+        if ones == 0 || ones == numbers.len() {
+            continue;
+        }
+        let bit = keep(numbers.len() - ones, ones);
+        numbers.retain(|&n| n >> shift & 1 == bit);
+    }
+    numbers[0]
+}
+
+impl Generator for Day3 {
+    /// Number of diagnostic report rows; the width grows with it so every
+    /// row can be different.
+    const DEFAULT_SIZE: usize = 1000;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let size = size.max(1);
+        let width = (usize::BITS - size.leading_zeros()) as usize + 2;
+        let width = width.max(5);
+
+        // the bit criteria only settle on one number if rows are unique
+        let mut seen = HashSet::new();
+        let mut numbers = vec![];
+        while numbers.len() < size {
+            let n = rng.below(1 << width);
+            if seen.insert(n) {
+                numbers.push(n);
+            }
+        }
+
+        let mut gamma = 0;
+        for shift in (0..width).rev() {
+            let ones = numbers.iter().filter(|&&n| n >> shift & 1 == 1).count();
+            gamma = gamma << 1 | (ones >= numbers.len() - ones) as usize;
+        }
+        let epsilon = !gamma & ((1 << width) - 1);
+
+        let oxygen = rating(numbers.clone(), width, |zeros, ones| {
+            (ones >= zeros) as usize
+        });
+        let co2 = rating(numbers.clone(), width, |zeros, ones| {
+            (ones < zeros) as usize
+        });
+
+        Generated {
+            input: numbers
+                .iter()
+                .map(|n| format!("{:0width$b}\n", n, width = width))
+                .collect(),
+            part_1: Some((gamma * epsilon).into()),
+            part_2: Some((oxygen * co2).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generate::check;
+
+    #[test]
+    fn generated_answers_match() {
+        for seed in 0..10 {
+            assert_eq!(check::<Day3>(seed, 200), Ok(()));
+        }
+    }
+}
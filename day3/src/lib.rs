@@ -0,0 +1,253 @@
+use common::{trace, ParseError, Solution, Span};
+use grid::Grid;
+
+mod generate;
+
+pub trait FromBinaryDigits {
+    fn as_binary_number(&self) -> usize;
+}
+
+impl FromBinaryDigits for [u8] {
+    fn as_binary_number(&self) -> usize {
+        let str_value = self
+            .iter()
+            .map(|v| format!("{}", v))
+            .collect::<Vec<_>>()
+            .join("");
+        usize::from_str_radix(&str_value, 2).expect("cannot convert vec of u8 to usize")
+    }
+}
+
+/// One diagnostic report number per row, one bit per column.
+pub type InputMatrix = Grid<u8>;
+
+fn parse(source: Span) -> Result<InputMatrix, ParseError> {
+    Grid::parse_chars(source, "binary digit", |c| match c {
+        '0' => Some(0),
+        '1' => Some(1),
+        _ => None,
+    })
+}
+
+pub fn count_ones_zeros(values: &[u8]) -> (usize, usize) {
+    let (zeros, ones): (Vec<&u8>, Vec<&u8>) = values.iter().partition(|&v| *v == 0);
+    (zeros.len(), ones.len())
+}
+
+pub fn part_1(input_matrix: &InputMatrix) -> usize {
+    let transposed = input_matrix.transpose();
+    let (gamma_digits, epsilon_digits): (Vec<u8>, Vec<u8>) = transposed
+        .rows()
+        .map(|column| {
+            let (zeros_count, ones_count) = count_ones_zeros(column);
+            let gamma_digit: u8 = if zeros_count > ones_count { 0 } else { 1 };
+            let epsilon_digit: u8 = if zeros_count > ones_count { 1 } else { 0 };
+            (gamma_digit, epsilon_digit)
+        })
+        .unzip();
+
+    let (gamma, epsilon) = (
+        gamma_digits.as_binary_number(),
+        epsilon_digits.as_binary_number(),
+    );
+    trace!(Info, "power", "gamma {}, epsilon {}", gamma, epsilon);
+    gamma * epsilon
+}
+
+pub fn decode_value(list: Vec<&[u8]>, bit_criteria_fn: fn(usize, usize, u8) -> bool) -> usize {
+    let width = list.first().map_or(0, |row| row.len());
+    let mut candidate_list = list;
+    let mut current_digit_idx = 0;
+    // candidates still left after the last bit are the same number
+    while candidate_list.len() > 1 && current_digit_idx < width {
+        let values = candidate_list
+            .iter()
+            .map(|candidate| candidate[current_digit_idx])
+            .collect::<Vec<_>>();
+        let (zeros_count, ones_count) = count_ones_zeros(&values);
+
+        // a bit every candidate shares can't tell them apart, and the co2
+        // criteria would throw them all away
+        if zeros_count > 0 && ones_count > 0 {
+            candidate_list.retain(|candidate| {
+                bit_criteria_fn(zeros_count, ones_count, candidate[current_digit_idx])
+            });
+        }
+        trace!(
+            Debug,
+            "rating",
+            "bit {}: {} zeros, {} ones, kept {} candidates",
+            current_digit_idx,
+            zeros_count,
+            ones_count,
+            candidate_list.len()
+        );
+        current_digit_idx += 1;
+    }
+
+    candidate_list
+        .first()
+        .map(|n| n.as_binary_number())
+        .expect("Cannot decode value")
+}
+
+pub fn oxygen_bit_criteria(zero_count: usize, one_count: usize, current_value: u8) -> bool {
+    use std::cmp::Ordering::*;
+    let keep_zero = current_value == 0;
+    let keep_one = current_value == 1;
+
+    match zero_count.cmp(&one_count) {
+        Less => keep_one,
+        Equal => keep_one,
+        Greater => keep_zero,
+    }
+}
+
+pub fn co2_bit_criteria(zero_count: usize, one_count: usize, current_value: u8) -> bool {
+    use std::cmp::Ordering::*;
+    let keep_zero = current_value == 0;
+    let keep_one = current_value == 1;
+
+    match zero_count.cmp(&one_count) {
+        Less => keep_zero,
+        Equal => keep_zero,
+        Greater => keep_one,
+    }
+}
+
+pub fn part_2(input_matrix: &InputMatrix) -> usize {
+    let initial = input_matrix.rows().collect::<Vec<_>>();
+    let oxygen = decode_value(initial.clone(), oxygen_bit_criteria);
+    let co2 = decode_value(initial.clone(), co2_bit_criteria);
+    trace!(Info, "rating", "oxygen {}, co2 {}", oxygen, co2);
+
+    oxygen * co2
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    const EXAMPLE: &'static str = include_str!("../example");
+
+    type Model = InputMatrix;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Model, ParseError> {
+        parse(Span::new(input))
+    }
+
+    fn part_1(input_matrix: &Self::Model) -> Self::Part1 {
+        part_1(input_matrix)
+    }
+
+    fn part_2(input_matrix: &Self::Model) -> Self::Part2 {
+        part_2(input_matrix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::Rng, property};
+    use std::collections::BTreeSet;
+
+    /// Distinct rows of random bits, as text.
+    fn random_report(rng: &mut Rng) -> Vec<String> {
+        let width = rng.between(1, 8);
+        let count = rng.between(1, (1 << width).min(40));
+        let mut rows = BTreeSet::new();
+        while rows.len() < count {
+            rows.insert(format!("{:0width$b}", rng.below(1 << width), width = width));
+        }
+        let mut rows = rows.into_iter().collect::<Vec<_>>();
+        rng.shuffle(&mut rows);
+        rows
+    }
+
+    fn ones_at(rows: &[String], idx: usize) -> usize {
+        rows.iter()
+            .filter(|row| row.as_bytes()[idx] == b'1')
+            .count()
+    }
+
+    fn naive_part_1(rows: &[String]) -> usize {
+        let (mut gamma, mut epsilon) = (String::new(), String::new());
+        for idx in 0..rows[0].len() {
+            let most_ones = ones_at(rows, idx) * 2 >= rows.len();
+            gamma.push(if most_ones { '1' } else { '0' });
+            epsilon.push(if most_ones { '0' } else { '1' });
+        }
+        usize::from_str_radix(&gamma, 2).unwrap() * usize::from_str_radix(&epsilon, 2).unwrap()
+    }
+
+    fn naive_rating(rows: &[String], most_common: bool) -> usize {
+        let mut rows = rows.to_vec();
+        for idx in 0..rows[0].len() {
+            if rows.len() == 1 {
+                break;
+            }
+            let ones = ones_at(&rows, idx);
+            if ones == 0 || ones == rows.len() {
+                continue;
+            }
+            let keep = match (ones * 2 >= rows.len(), most_common) {
+                (true, true) | (false, false) => b'1',
+                _ => b'0',
+            };
+            rows.retain(|row| row.as_bytes()[idx] == keep);
+        }
+        usize::from_str_radix(&rows[0], 2).unwrap()
+    }
+
+    #[test]
+    fn example() {
+        let input_matrix = Day3::parse(Day3::EXAMPLE).unwrap();
+        assert_eq!(part_1(&input_matrix), 198);
+        assert_eq!(part_2(&input_matrix), 230);
+    }
+
+    #[test]
+    fn decode_value_applies_bit_criteria() {
+        let input_matrix = Day3::parse(Day3::EXAMPLE).unwrap();
+        let initial = input_matrix.rows().collect::<Vec<_>>();
+        assert_eq!(decode_value(initial.clone(), oxygen_bit_criteria), 23);
+        assert_eq!(decode_value(initial, co2_bit_criteria), 10);
+    }
+
+    #[test]
+    fn decode_value_breaks_ties() {
+        let a = [1, 0];
+        let b = [0, 1];
+        assert_eq!(decode_value(vec![&a, &b], oxygen_bit_criteria), 0b10);
+        assert_eq!(decode_value(vec![&a, &b], co2_bit_criteria), 0b01);
+    }
+
+    #[test]
+    fn decode_value_skips_shared_bits() {
+        let a = [0, 1, 1];
+        let b = [0, 1, 0];
+        assert_eq!(decode_value(vec![&a, &b], co2_bit_criteria), 0b010);
+    }
+
+    #[test]
+    fn decode_value_settles_on_duplicates() {
+        let a = [1, 0];
+        assert_eq!(decode_value(vec![&a, &a], oxygen_bit_criteria), 0b10);
+        assert_eq!(decode_value(vec![&a, &a], co2_bit_criteria), 0b10);
+    }
+
+    #[test]
+    fn matches_naive_solution() {
+        property::check(200, |rng| {
+            let rows = random_report(rng);
+            let input_matrix = Day3::parse(&rows.join("\n")).unwrap();
+            assert_eq!(part_1(&input_matrix), naive_part_1(&rows));
+            assert_eq!(
+                part_2(&input_matrix),
+                naive_rating(&rows, true) * naive_rating(&rows, false)
+            );
+        });
+    }
+}
@@ -1,3 +1,5 @@
+use common::{ParseError, Span};
+
 #[derive(Clone, Copy, Debug)]
 pub enum BoardValue {
     Unmarked(u8),
@@ -41,13 +43,11 @@ impl BingoBoard {
     }
 
     fn transpose(&self) -> Self {
-        let mut new_values = self.values.clone();
-        let n = self.values.len();
+        let mut new_values = self.values;
 
-        for row_idx in 0..n {
-            for col_idx in 0..n {
-                let value = self.values[row_idx][col_idx];
-                new_values[col_idx][row_idx] = value;
+        for (row_idx, row) in self.values.iter().enumerate() {
+            for (col_idx, value) in row.iter().enumerate() {
+                new_values[col_idx][row_idx] = *value;
             }
         }
 
@@ -72,28 +72,32 @@ impl BingoBoard {
     pub fn unmarked_iter(&self) -> impl Iterator<Item = &BoardValue> {
         self.values
             .iter()
-            .map(|row| row.iter())
-            .flatten()
+            .flat_map(|row| row.iter())
             .filter(|v| !v.is_marked())
     }
 
-    pub fn from_str(source: &str) -> Self {
-        let values_vec = source
-            .lines()
-            .map(|row_str| {
-                row_str
-                    .split_whitespace()
-                    .map(|value_str| value_str.parse::<u8>().expect("cannot parse value as u8"))
-                    .collect::<Vec<_>>()
-            })
-            .collect::<Vec<_>>();
+    pub(crate) fn parse(source: Span) -> Result<Self, ParseError> {
         let mut values = [[BoardValue::Unmarked(0); 5]; 5];
-        for row_idx in 0..5 {
-            for col_idx in 0..5 {
-                values[row_idx][col_idx] = BoardValue::Unmarked(values_vec[row_idx][col_idx]);
+        let mut rows = source.trim().lines();
+        for row in values.iter_mut() {
+            let row_str = rows
+                .next()
+                .ok_or_else(|| source.end().error("5 board rows"))?;
+            let mut numbers = row_str.split_whitespace();
+            for value in row.iter_mut() {
+                let number = numbers
+                    .next()
+                    .ok_or_else(|| row_str.end().error("5 numbers per row"))?;
+                *value = BoardValue::Unmarked(number.parse::<u8>("board number")?);
+            }
+            if let Some(extra) = numbers.next() {
+                return Err(extra.error("end of row"));
             }
         }
+        if let Some(extra) = rows.next() {
+            return Err(extra.error("end of board"));
+        }
 
-        Self { values }
+        Ok(Self { values })
     }
 }
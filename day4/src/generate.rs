@@ -0,0 +1,72 @@
+use common::generate::{Generated, Generator, Rng};
+
+use crate::Day4;
+
+const NUMBERS: usize = 100;
+
+impl Generator for Day4 {
+    /// Number of bingo boards.
+    const DEFAULT_SIZE: usize = 100;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut drawn = (0..NUMBERS).collect::<Vec<_>>();
+        rng.shuffle(&mut drawn);
+        let mut turn = vec![0; NUMBERS];
+        for (idx, &n) in drawn.iter().enumerate() {
+            turn[n] = idx;
+        }
+
+        let mut input = drawn
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        input += "\n";
+
+        // a board wins on the turn its quickest row or column is complete,
+        // and the earliest board in the input wins ties
+        let mut wins = vec![];
+        for board_idx in 0..size.max(1) {
+            let mut numbers = (0..NUMBERS).collect::<Vec<_>>();
+            rng.shuffle(&mut numbers);
+            numbers.truncate(25);
+
+            input += "\n";
+            for row in numbers.chunks(5) {
+                let row = row.iter().map(|n| format!("{:2}", n)).collect::<Vec<_>>();
+                input += &row.join(" ");
+                input += "\n";
+            }
+
+            let complete = |line: &mut dyn Iterator<Item = usize>| {
+                line.map(|idx| turn[numbers[idx]]).max().unwrap()
+            };
+            let won_on = (0..5)
+                .map(|row| complete(&mut (0..5).map(|col| row * 5 + col)))
+                .chain((0..5).map(|col| complete(&mut (0..5).map(|row| row * 5 + col))))
+                .min()
+                .unwrap();
+            let unmarked = numbers.iter().filter(|&&n| turn[n] > won_on).sum::<usize>();
+            wins.push((won_on, board_idx, unmarked * drawn[won_on]));
+        }
+
+        Generated {
+            input,
+            part_1: wins.iter().min().map(|win| win.2.into()),
+            part_2: wins.iter().max().map(|win| win.2.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generate::check;
+
+    #[test]
+    fn generated_answers_match() {
+        for seed in 0..10 {
+            assert_eq!(check::<Day4>(seed, 20), Ok(()));
+        }
+    }
+}
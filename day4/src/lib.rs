@@ -0,0 +1,139 @@
+use bingo::BingoBoard;
+use common::{
+    grammar::{list, map, number, sections, two_sections},
+    trace, ParseError, Solution, Span,
+};
+
+pub mod bingo;
+mod generate;
+
+#[derive(Clone, Debug)]
+pub struct Input {
+    pub drawn_numbers: Vec<u8>,
+    pub boards: Vec<BingoBoard>,
+}
+
+impl Input {
+    /// The drawn numbers, then the boards, separated by blank lines.
+    fn parse(input: Span) -> Result<Self, ParseError> {
+        let input_grammar = two_sections(
+            "boards after the drawn numbers",
+            list(",", number("drawn number")),
+            sections(BingoBoard::parse),
+        );
+        map(input_grammar, |(drawn_numbers, boards)| Self {
+            drawn_numbers,
+            boards,
+        })(input)
+    }
+}
+
+pub fn part_1(input: &Input) -> usize {
+    let mut input = input.clone();
+    for (draw, &drawn_number) in input.drawn_numbers.iter().enumerate() {
+        for (idx, board) in input.boards.iter_mut().enumerate() {
+            board.mark_number(drawn_number);
+            if board.is_winning() {
+                trace!(
+                    Info,
+                    "bingo",
+                    "board {} won on draw {} ({})",
+                    idx + 1,
+                    draw + 1,
+                    drawn_number
+                );
+                let unmarked_sum: usize =
+                    board.unmarked_iter().map(|v| v.get_number() as usize).sum();
+                return unmarked_sum * drawn_number as usize;
+            }
+        }
+    }
+
+    panic!("No board won")
+}
+
+pub fn part_2(input: &Input) -> usize {
+    let mut input = input.clone();
+
+    let mut last_winning_board = Option::None;
+    let mut last_winning_number = Option::None;
+    for (draw, &drawn_number) in input.drawn_numbers.iter().enumerate() {
+        for (idx, board) in input.boards.iter_mut().enumerate() {
+            if !board.is_winning() {
+                board.mark_number(drawn_number);
+                if board.is_winning() {
+                    trace!(
+                        Info,
+                        "bingo",
+                        "board {} won on draw {} ({})",
+                        idx + 1,
+                        draw + 1,
+                        drawn_number
+                    );
+                    last_winning_board = Option::Some(board.clone());
+                    last_winning_number = Option::Some(drawn_number);
+                }
+            }
+        }
+    }
+
+    match (last_winning_board, last_winning_number) {
+        (Some(b), Some(n)) => {
+            let unmarked_sum: usize = b.unmarked_iter().map(|v| v.get_number() as usize).sum();
+            unmarked_sum * n as usize
+        }
+        _ => panic!("No board won"),
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    const EXAMPLE: &'static str = include_str!("../example");
+
+    type Model = Input;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Model, ParseError> {
+        Input::parse(Span::new(input))
+    }
+
+    fn part_1(input: &Self::Model) -> Self::Part1 {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Model) -> Self::Part2 {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = Day4::parse(Day4::EXAMPLE).unwrap();
+        assert_eq!(part_1(&input), 4512);
+        assert_eq!(part_2(&input), 1924);
+    }
+
+    #[test]
+    fn reports_bad_board_number() {
+        let input = "7,4,9
+
+22 13 17 11  0
+ 8  2 23  x 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+";
+        let err = Day4::parse(input).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 11: expected board number, found \"x\""
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use common::generate::{Generated, Generator, Rng};
+
+use crate::Day5;
+
+impl Generator for Day5 {
+    /// Number of vent lines; the ocean floor grows with it.
+    const DEFAULT_SIZE: usize = 500;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let extent = (size * 2).clamp(10, 1000);
+        let mut input = String::new();
+        let mut straight = HashMap::new();
+        let mut all = HashMap::new();
+
+        for _ in 0..size {
+            let (x, y) = (rng.below(extent), rng.below(extent));
+            let len = rng.between(1, extent / 2);
+            // 0: horizontal, 1: vertical, 2 and 3: the two diagonals
+            let kind = rng.below(4);
+            let (dx, dy): (isize, isize) = match kind {
+                0 => (1, 0),
+                1 => (0, 1),
+                2 => (1, 1),
+                _ => (1, -1),
+            };
+            let steps = (0..=len)
+                .take_while(|&step| {
+                    let x = x as isize + dx * step as isize;
+                    let y = y as isize + dy * step as isize;
+                    x < extent as isize && (0..extent as isize).contains(&y)
+                })
+                .count()
+                - 1;
+            let end = (
+                (x as isize + dx * steps as isize) as usize,
+                (y as isize + dy * steps as isize) as usize,
+            );
+
+            // write some lines back to front
+            let (from, to) = if rng.chance(1, 2) {
+                ((x, y), end)
+            } else {
+                (end, (x, y))
+            };
+            input += &format!("{},{} -> {},{}\n", from.0, from.1, to.0, to.1);
+
+            for step in 0..=steps as isize {
+                let point = (x as isize + dx * step, y as isize + dy * step);
+                *all.entry(point).or_insert(0) += 1;
+                // a single point counts as horizontal too
+                if kind < 2 || steps == 0 {
+                    *straight.entry(point).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let overlaps = |counts: &HashMap<_, usize>| counts.values().filter(|&&c| c > 1).count();
+        Generated {
+            input,
+            part_1: Some(overlaps(&straight).into()),
+            part_2: Some(overlaps(&all).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generate::check;
+
+    #[test]
+    fn generated_answers_match() {
+        for seed in 0..10 {
+            assert_eq!(check::<Day5>(seed, 50), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use common::{
+    grammar::{lines, map, number, pair, verify},
+    trace, ParseError, Solution, Span,
+};
+use grid::Grid;
+
+mod generate;
+pub mod visual;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    /// `x,y`
+    fn parse(source: Span) -> Result<Point, ParseError> {
+        let point = pair(
+            ",",
+            "`x,y` point",
+            number("point x coord"),
+            number("point y coord"),
+        );
+        map(point, |(x, y)| Point { x, y })(source)
+    }
+}
+
+#[derive(Debug)]
+pub struct Line {
+    pub from: Point,
+    pub to: Point,
+}
+
+pub struct PointsIterator {
+    curr: Point,
+    len: isize,
+    x_step: isize,
+    y_step: isize,
+}
+
+impl Iterator for PointsIterator {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len < 0 {
+            None
+        } else {
+            let current = self.curr;
+
+            self.len -= 1;
+            self.curr = Point {
+                x: (self.curr.x as isize + self.x_step) as usize,
+                y: (self.curr.y as isize + self.y_step) as usize,
+            };
+
+            Some(current)
+        }
+    }
+}
+
+impl Line {
+    /// `x1,y1 -> x2,y2`, horizontal, vertical or diagonal.
+    fn parse(source: Span) -> Result<Line, ParseError> {
+        let line = pair(" -> ", "`x1,y1 -> x2,y2` line", Point::parse, Point::parse);
+        let line = map(line, |(from, to)| Line { from, to });
+        verify(line, "horizontal, vertical or 45° line", |line| {
+            line.is_horizontal() || line.is_vertical() || line.is_diagonal()
+        })(source)
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.from.x == self.to.x
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.from.y == self.to.y
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        self.from.x.abs_diff(self.to.x) == self.from.y.abs_diff(self.to.y)
+    }
+
+    pub fn points_iter(&self) -> impl Iterator<Item = Point> {
+        use std::cmp::Ordering::*;
+        let find_diff = |a: usize, b: usize| match a.cmp(&b) {
+            Less => 1,
+            Equal => 0,
+            Greater => -1,
+        };
+        let x_step = find_diff(self.from.x, self.to.x);
+        let y_step = find_diff(self.from.y, self.to.y);
+        let len = (self.from.x as isize - self.to.x as isize)
+            .abs()
+            .max((self.from.y as isize - self.to.y as isize).abs());
+
+        PointsIterator {
+            curr: self.from,
+            len,
+            x_step,
+            y_step,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Input {
+    pub lines: Vec<Line>,
+}
+
+impl Input {
+    fn parse(input: Span) -> Result<Self, ParseError> {
+        let lines = verify(lines(Line::parse), "at least one line", |lines| {
+            !lines.is_empty()
+        });
+        map(lines, |lines| Input { lines })(input)
+    }
+
+    pub fn width(&self) -> usize {
+        self.lines
+            .iter()
+            .map(|line| line.from.x.max(line.to.x))
+            .max()
+            .unwrap()
+            + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.lines
+            .iter()
+            .map(|line| line.from.y.max(line.to.y))
+            .max()
+            .unwrap()
+            + 1
+    }
+}
+
+/// How many vent lines cross each point.
+pub type Map = Grid<usize>;
+
+fn draw<'a>(input: &Input, lines: impl Iterator<Item = &'a Line>) -> Map {
+    let mut map = Map::filled(input.width(), input.height(), 0);
+    let mut drawn = 0;
+    for line in lines {
+        for line_point in line.points_iter() {
+            map[(line_point.x, line_point.y)] += 1;
+        }
+        drawn += 1;
+    }
+    trace!(
+        Info,
+        "vents",
+        "drew {} lines on a {}x{} map",
+        drawn,
+        map.width(),
+        map.height()
+    );
+    map
+}
+
+fn overlaps(map: &Map) -> usize {
+    let overlaps = map.iter().filter(|&&c| c > 1).count();
+    trace!(Info, "vents", "{} points where lines overlap", overlaps);
+    overlaps
+}
+
+pub fn part_1(input: &Input) -> usize {
+    let straight = input
+        .lines
+        .iter()
+        .filter(|line| line.is_horizontal() || line.is_vertical());
+    let map = draw(input, straight);
+
+    overlaps(&map)
+}
+
+pub fn part_2(input: &Input) -> usize {
+    let map = draw(input, input.lines.iter());
+
+    overlaps(&map)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    const EXAMPLE: &'static str = include_str!("../example");
+
+    type Model = Input;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Model, ParseError> {
+        Input::parse(Span::new(input))
+    }
+
+    fn part_1(input: &Self::Model) -> Self::Part1 {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Model) -> Self::Part2 {
+        part_2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(line: &str) -> Vec<(usize, usize)> {
+        Line::parse(Span::new(line))
+            .unwrap()
+            .points_iter()
+            .map(|p| (p.x, p.y))
+            .collect()
+    }
+
+    #[test]
+    fn example() {
+        let input = Day5::parse(Day5::EXAMPLE).unwrap();
+        assert_eq!(part_1(&input), 5);
+        assert_eq!(part_2(&input), 12);
+    }
+
+    #[test]
+    fn reports_bad_point() {
+        let input = "0,9 -> 5,9\n8,0 -> 0,x\n";
+        let err = Day5::parse(input).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 10: expected point y coord, found \"x\""
+        );
+    }
+
+    #[test]
+    fn rejects_input_without_a_grid() {
+        let err = Day5::parse("0,0 -> 3,1\n").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 1: expected horizontal, vertical or 45° line, found \"0,0 -> 3,1\""
+        );
+        let err = Day5::parse("").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert_eq!(err.expected, "at least one line");
+    }
+
+    #[test]
+    fn points_iter_straight() {
+        assert_eq!(points("0,9 -> 2,9"), vec![(0, 9), (1, 9), (2, 9)]);
+        assert_eq!(points("7,2 -> 7,0"), vec![(7, 2), (7, 1), (7, 0)]);
+        assert_eq!(points("4,4 -> 4,4"), vec![(4, 4)]);
+    }
+
+    #[test]
+    fn points_iter_diagonals() {
+        assert_eq!(points("1,1 -> 3,3"), vec![(1, 1), (2, 2), (3, 3)]);
+        assert_eq!(points("9,7 -> 7,9"), vec![(9, 7), (8, 8), (7, 9)]);
+        assert_eq!(points("2,2 -> 0,0"), vec![(2, 2), (1, 1), (0, 0)]);
+        assert_eq!(points("0,2 -> 2,0"), vec![(0, 2), (1, 1), (2, 0)]);
+    }
+}
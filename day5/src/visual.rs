@@ -0,0 +1,78 @@
+use grid::{
+    image::Rgb,
+    visual::{Cell, Scene, Visual},
+    Grid,
+};
+
+use crate::{draw, Day5, Input, Map};
+
+/// Draws the vent lines onto the map one at a time, diagonals included.
+pub struct Rasterize {
+    input: Input,
+    map: Map,
+    drawn: usize,
+}
+
+impl Rasterize {
+    pub fn new(input: Input) -> Self {
+        let map = Map::filled(input.width(), input.height(), 0);
+        Rasterize {
+            input,
+            map,
+            drawn: 0,
+        }
+    }
+}
+
+impl Scene for Rasterize {
+    fn frame(&self) -> Grid<Cell> {
+        let mut frame = self.map.map(|_, &count| match count {
+            0 => Cell::EMPTY,
+            1 => Cell::new('·', 33),
+            2 => Cell::new('▒', 220),
+            _ => Cell::new('█', 196),
+        });
+        if let Some(line) = self.drawn.checked_sub(1).map(|idx| &self.input.lines[idx]) {
+            for point in line.points_iter() {
+                frame[(point.x, point.y)].colour = Some(46);
+            }
+        }
+        frame
+    }
+
+    fn advance(&mut self) -> bool {
+        let line = match self.input.lines.get(self.drawn) {
+            Some(line) => line,
+            None => return false,
+        };
+        for point in line.points_iter() {
+            self.map[(point.x, point.y)] += 1;
+        }
+        self.drawn += 1;
+        true
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "line {}/{}, {} overlapping points",
+            self.drawn,
+            self.input.lines.len(),
+            self.map.iter().filter(|&&c| c > 1).count()
+        )
+    }
+}
+
+impl Visual for Day5 {
+    type Scene = Rasterize;
+
+    fn scene(input: Self::Model) -> Self::Scene {
+        Rasterize::new(input)
+    }
+
+    /// Heatmap of how many lines, diagonals included, cross each point.
+    fn image(input: &Self::Model) -> Grid<Rgb> {
+        let map = draw(input, input.lines.iter());
+        let max = map.iter().copied().max().unwrap_or(0);
+        map.map(|_, &count| Rgb::heat(count, max))
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use common::generate::{Generated, Generator, Rng};
+
+use crate::Day6;
+
+/// How many fish a single fish with `timer` turns into after `days`, counted
+/// by following its offspring instead of by age bucket.
+fn descendants(timer: usize, days: usize, memo: &mut HashMap<(usize, usize), usize>) -> usize {
+    if days <= timer {
+        return 1;
+    }
+    if let Some(&count) = memo.get(&(timer, days)) {
+        return count;
+    }
+    let days_left = days - timer - 1;
+    let count = descendants(6, days_left, memo) + descendants(8, days_left, memo);
+    memo.insert((timer, days), count);
+    count
+}
+
+impl Generator for Day6 {
+    /// Number of fish.
+    const DEFAULT_SIZE: usize = 300;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let timers = (0..size.max(1))
+            .map(|_| rng.between(1, 5))
+            .collect::<Vec<_>>();
+
+        let mut memo = HashMap::new();
+        let mut population = |days| -> usize {
+            timers
+                .iter()
+                .map(|&timer| descendants(timer, days, &mut memo))
+                .sum()
+        };
+        let part_1 = population(80);
+        let part_2 = population(256);
+
+        let timers = timers.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+        Generated {
+            input: timers.join(",") + "\n",
+            part_1: Some(part_1.into()),
+            part_2: Some(part_2.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generate::check;
+
+    #[test]
+    fn generated_answers_match() {
+        for seed in 0..10 {
+            assert_eq!(check::<Day6>(seed, 50), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use common::{
+    arith::{Arithmetic, BigUint, CheckedCount, Count, Overflow},
+    grammar::{list, number, verify},
+    snapshot::{Simulate, Snapshot},
+    trace, Answer, ParseError, Simulation, Solution, Span,
+};
+use std::collections::HashMap;
+
+mod generate;
+
+/// Number of fish by timer, counted with `C`.
+pub struct LanternfishPool<C = usize> {
+    map: HashMap<u8, C>,
+}
+
+impl<C: Count> LanternfishPool<C> {
+    pub fn from_slice(input: &[u8]) -> Self {
+        let mut map = HashMap::new();
+        for &fish in input.iter() {
+            map.entry(fish)
+                .or_insert_with(|| C::from_usize(0))
+                .increase(&C::from_usize(1));
+        }
+
+        LanternfishPool { map }
+    }
+
+    pub fn count(&self) -> C {
+        let mut count = C::from_usize(0);
+        for fish in self.map.values() {
+            count.increase(fish);
+        }
+        count
+    }
+}
+
+impl<C: Count> Simulation for LanternfishPool<C> {
+    /// Ages every fish by a day.
+    fn step(&mut self) {
+        let mut new_map = HashMap::new();
+        let mut add = |age, count: &C| {
+            new_map
+                .entry(age)
+                .or_insert_with(|| C::from_usize(0))
+                .increase(count)
+        };
+        for (&age, count) in self.map.iter() {
+            if age == 0 {
+                add(6, count);
+                add(8, count);
+            } else {
+                add(age - 1, count);
+            }
+        }
+        self.map = new_map;
+    }
+}
+
+/// Counted with `BigUint`, since a run long enough to be worth saving
+/// outgrows any machine word.
+impl Snapshot for LanternfishPool<BigUint> {
+    /// One `timer <t> <count>` line per timer, in order.
+    fn save(&self, out: &mut String) {
+        let mut timers = self.map.iter().collect::<Vec<_>>();
+        timers.sort();
+        for (timer, count) in timers {
+            out.push_str(&format!("timer {} {}\n", timer, count));
+        }
+    }
+
+    fn restore(input: Span) -> Result<Self, ParseError> {
+        let mut map = HashMap::new();
+        for line in input.lines() {
+            let mut fields = line
+                .strip_prefix("timer ")
+                .ok_or_else(|| line.error("`timer <t> <count>` line"))?
+                .split(" ");
+            let (timer, count) = match (fields.next(), fields.next(), fields.next()) {
+                (Some(timer), Some(count), None) => (timer, count),
+                _ => return Err(line.error("`timer <t> <count>` line")),
+            };
+            let age = match timer.parse::<u8>("fish timer")? {
+                age @ 0..=8 => age,
+                _ => return Err(timer.error("fish timer between 0 and 8")),
+            };
+            map.entry(age)
+                .or_insert_with(BigUint::default)
+                .increase(&count.parse("fish count")?);
+        }
+        Ok(LanternfishPool { map })
+    }
+}
+
+fn count_fish(input: &[u8], days: usize) -> usize {
+    let mut pool = LanternfishPool::from_slice(input);
+    for day in 1..=days {
+        pool.step();
+        trace!(Debug, "pool", "day {}: {} fish", day, pool.count());
+    }
+    trace!(Info, "pool", "{} fish after {} days", pool.count(), days);
+    pool.count()
+}
+
+pub fn part_1(input: &[u8]) -> usize {
+    count_fish(input, 80)
+}
+
+pub fn part_2(input: &[u8]) -> usize {
+    count_fish(input, 256)
+}
+
+/// Counts the fish after `days` days, stopping at the first day on which the
+/// count no longer fits in `C`.
+pub fn count_after<C: Count>(input: &[u8], days: usize) -> Result<Answer, Overflow> {
+    let mut pool = LanternfishPool::<C>::from_slice(input);
+    for day in 1..=days {
+        pool.step();
+        if let Some(overflow) = pool.count().overflow() {
+            return Err(overflow.clone().at(format!("day {}", day)));
+        }
+    }
+    pool.count().answer()
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    const EXAMPLE: &'static str = include_str!("../example");
+
+    type Model = Vec<u8>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Model, ParseError> {
+        let timer = verify(
+            number("fish timer"),
+            "fish timer between 0 and 8",
+            |&timer| timer <= 8,
+        );
+        list(",", timer)(Span::new(input).trim())
+    }
+
+    fn part_1(input: &Self::Model) -> Self::Part1 {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Model) -> Self::Part2 {
+        part_2(input)
+    }
+
+    fn solve(input: &Self::Model, part: u8, arithmetic: Arithmetic) -> Result<Answer, Overflow> {
+        let days = if part == 1 { 80 } else { 256 };
+        match arithmetic {
+            Arithmetic::Native => count_after::<usize>(input, days),
+            Arithmetic::Checked => count_after::<CheckedCount>(input, days),
+            Arithmetic::Big => count_after::<BigUint>(input, days),
+        }
+    }
+}
+
+impl Simulate for Day6 {
+    type State = LanternfishPool<BigUint>;
+
+    fn start(input: &Self::Model) -> Self::State {
+        LanternfishPool::from_slice(input)
+    }
+
+    fn summary(pool: &Self::State) -> Answer {
+        pool.count().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{property, snapshot};
+
+    /// Ages every fish one by one.
+    fn naive_step(fish: &mut Vec<u8>) {
+        let mut born = 0;
+        for timer in fish.iter_mut() {
+            if *timer == 0 {
+                *timer = 6;
+                born += 1;
+            } else {
+                *timer -= 1;
+            }
+        }
+        fish.resize(fish.len() + born, 8);
+    }
+
+    #[test]
+    fn example() {
+        let input = Day6::parse(Day6::EXAMPLE).unwrap();
+        assert_eq!(part_1(&input), 5934);
+        assert_eq!(part_2(&input), 26984457539);
+    }
+
+    #[test]
+    fn big_counts_outgrow_checked_ones() {
+        let input = Day6::parse(Day6::EXAMPLE).unwrap();
+        assert_eq!(
+            count_after::<CheckedCount>(&input, 256),
+            Ok(Answer::Number(26984457539))
+        );
+        let overflow = count_after::<CheckedCount>(&input, 500).unwrap_err();
+        assert_eq!(overflow.context.as_deref(), Some("day 490"));
+        assert_eq!(
+            count_after::<BigUint>(&input, 500),
+            Ok(Answer::Number(45920814377410229085))
+        );
+    }
+
+    #[test]
+    fn snapshot_resumes_where_it_stopped() {
+        let input = Day6::parse(Day6::EXAMPLE).unwrap();
+        let mut pool = Day6::start(&input);
+        pool.run(40);
+        let text = snapshot::write::<Day6>(40, &pool);
+        let (step, mut resumed) = snapshot::read::<Day6>(&text).unwrap();
+        assert_eq!(step, 40);
+        assert_eq!(snapshot::write::<Day6>(step, &resumed), text);
+        resumed.run(216);
+        assert_eq!(resumed.count(), BigUint::from_usize(26984457539));
+    }
+
+    #[test]
+    fn snapshot_counts_past_machine_words() {
+        let input = Day6::parse(Day6::EXAMPLE).unwrap();
+        let mut pool = Day6::start(&input);
+        pool.run(400);
+        let text = snapshot::write::<Day6>(400, &pool);
+        let (_, mut resumed) = snapshot::read::<Day6>(&text).unwrap();
+        resumed.run(100);
+        assert_eq!(resumed.count().to_string(), "45920814377410229085");
+        resumed.run(100);
+        assert_eq!(
+            Day6::summary(&resumed),
+            count_after::<BigUint>(&input, 600).unwrap()
+        );
+    }
+
+    #[test]
+    fn history_tracks_population() {
+        let input = Day6::parse(Day6::EXAMPLE).unwrap();
+        let mut pool = LanternfishPool::<usize>::from_slice(&input);
+        let counts = pool.history(18, LanternfishPool::count);
+        assert_eq!(counts.len(), 19);
+        assert_eq!(counts[0], 5);
+        assert_eq!(counts[18], 26);
+    }
+
+    #[test]
+    fn matches_naive_simulation() {
+        property::check(100, |rng| {
+            let mut fish = (0..rng.between(1, 10))
+                .map(|_| rng.below(9) as u8)
+                .collect::<Vec<_>>();
+            let mut pool = LanternfishPool::<usize>::from_slice(&fish);
+            for _ in 0..rng.between(0, 60) {
+                naive_step(&mut fish);
+                pool.step();
+                assert_eq!(pool.count(), fish.len());
+            }
+        });
+    }
+}
@@ -0,0 +1,19 @@
+use std::process::Command;
+
+/// Days with a simulation go through `snapshot::main`, which must leave
+/// `--trace` to the usual entry point so it picks up `--format`.
+#[test]
+fn trace_follows_format() {
+    let output = Command::new(env!("CARGO_BIN_EXE_day6"))
+        .args(["--format", "json", "--trace", "info"])
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/example"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let events = String::from_utf8(output.stderr).unwrap();
+    assert!(!events.is_empty());
+    for event in events.lines() {
+        assert!(event.starts_with("{\"day\":6,"), "{}", event);
+    }
+}
@@ -0,0 +1,57 @@
+use common::generate::{Generated, Generator, Rng};
+
+use crate::Day7;
+
+fn fuel(crabs: &[usize], target: usize, cost: fn(usize) -> usize) -> usize {
+    crabs.iter().map(|&crab| cost(crab.abs_diff(target))).sum()
+}
+
+impl Generator for Day7 {
+    /// Number of crabs.
+    const DEFAULT_SIZE: usize = 1000;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let size = size.max(1);
+        let spread = (size * 2).max(16);
+        let mut crabs = (0..size)
+            // skewed towards the start like the real input
+            .map(|_| {
+                let range = rng.between(1, spread);
+                rng.below(range)
+            })
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut crabs);
+
+        // linear cost is lowest at the median, triangular cost within half a
+        // step of the mean
+        let mut sorted = crabs.clone();
+        sorted.sort_unstable();
+        let median = sorted[(size - 1) / 2];
+        let mean = crabs.iter().sum::<usize>() / size;
+        let triangular = |d: usize| d * (d + 1) / 2;
+        let part_2 = (mean.saturating_sub(1)..=mean + 1)
+            .map(|target| fuel(&crabs, target, triangular))
+            .min()
+            .unwrap();
+
+        let crabs_str = crabs.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        Generated {
+            input: crabs_str.join(",") + "\n",
+            part_1: Some(fuel(&crabs, median, |d| d).into()),
+            part_2: Some(part_2.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generate::check;
+
+    #[test]
+    fn generated_answers_match() {
+        for seed in 0..10 {
+            assert_eq!(check::<Day7>(seed, 100), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use common::{
+    grammar::{list, number},
+    trace, ParseError, Solution, Span,
+};
+
+mod generate;
+
+pub fn find_cheapest_position(positions: &[usize], cost_fn: fn(usize) -> usize) -> usize {
+    // the best spot doesn't have to be occupied by a crab
+    let min = positions.iter().min().copied().unwrap_or(0);
+    let max = positions.iter().max().copied().unwrap_or(0);
+    let (cost, position) = (min..=max)
+        .map(|i| {
+            let cost: usize = positions
+                .iter()
+                .map(|&crab| (i as isize - crab as isize).unsigned_abs())
+                .map(&cost_fn)
+                .sum();
+            (cost, i)
+        })
+        .min()
+        .unwrap();
+    trace!(
+        Info,
+        "crabs",
+        "cheapest position {} costs {}",
+        position,
+        cost
+    );
+    cost
+}
+
+pub fn incremental_cost(distance: usize) -> usize {
+    (0..=distance).sum()
+}
+
+pub fn part_1(crabs: &[usize]) -> usize {
+    find_cheapest_position(crabs, std::convert::identity)
+}
+
+pub fn part_2(crabs: &[usize]) -> usize {
+    find_cheapest_position(crabs, incremental_cost)
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    const EXAMPLE: &'static str = include_str!("../example");
+
+    type Model = Vec<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Model, ParseError> {
+        list(",", number("crab position"))(Span::new(input).trim())
+    }
+
+    fn part_1(crabs: &Self::Model) -> Self::Part1 {
+        part_1(crabs)
+    }
+
+    fn part_2(crabs: &Self::Model) -> Self::Part2 {
+        part_2(crabs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::property;
+
+    /// Walks every crab to every position one step at a time.
+    fn naive_fuel(crabs: &[usize], increasing: bool) -> usize {
+        let max = *crabs.iter().max().unwrap();
+        (0..=max)
+            .map(|target| {
+                let mut fuel = 0;
+                for &crab in crabs {
+                    let mut step_cost = 1;
+                    let mut position = crab;
+                    while position != target {
+                        position = if position < target {
+                            position + 1
+                        } else {
+                            position - 1
+                        };
+                        fuel += step_cost;
+                        if increasing {
+                            step_cost += 1;
+                        }
+                    }
+                }
+                fuel
+            })
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn example() {
+        let crabs = Day7::parse(Day7::EXAMPLE).unwrap();
+        assert_eq!(part_1(&crabs), 37);
+        assert_eq!(part_2(&crabs), 168);
+    }
+
+    #[test]
+    fn matches_naive_solution() {
+        property::check(200, |rng| {
+            let crabs = (0..rng.between(1, 20))
+                .map(|_| rng.below(50))
+                .collect::<Vec<_>>();
+            assert_eq!(part_1(&crabs), naive_fuel(&crabs, false));
+            assert_eq!(part_2(&crabs), naive_fuel(&crabs, true));
+        });
+    }
+}
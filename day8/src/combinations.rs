@@ -22,9 +22,9 @@ fn _combinations(
                 let foo_r = _combinations(
                     from_list,
                     to_list,
-                    vec![acc.clone(), vec![(*from, *to)]].concat(),
+                    [acc.clone(), vec![(*from, *to)]].concat(),
                 );
-                r = vec![r, foo_r].concat();
+                r = [r, foo_r].concat();
             }
             r
         }
@@ -0,0 +1,73 @@
+use common::generate::{Generated, Generator, Rng};
+
+use crate::Day8;
+
+/// Lit segments for each digit with the standard wiring.
+const DIGITS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// A digit as it shows up with a scrambled `wiring`, segments in any order.
+fn scramble(rng: &mut Rng, digit: usize, wiring: &[char]) -> String {
+    let mut segments = DIGITS[digit]
+        .chars()
+        .map(|c| wiring[(c as u8 - b'a') as usize])
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut segments);
+    segments.into_iter().collect()
+}
+
+impl Generator for Day8 {
+    /// Number of display entries.
+    const DEFAULT_SIZE: usize = 200;
+
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let mut input = String::new();
+        let mut easy_digits = 0;
+        let mut output_sum = 0;
+
+        for _ in 0..size {
+            let mut wiring = ('a'..='g').collect::<Vec<_>>();
+            rng.shuffle(&mut wiring);
+
+            let mut digits = (0..10).collect::<Vec<_>>();
+            rng.shuffle(&mut digits);
+            let patterns = digits
+                .iter()
+                .map(|&digit| scramble(rng, digit, &wiring))
+                .collect::<Vec<_>>();
+
+            let output = (0..4).map(|_| rng.below(10)).collect::<Vec<_>>();
+            easy_digits += output
+                .iter()
+                .filter(|&&digit| matches!(digit, 1 | 4 | 7 | 8))
+                .count();
+            output_sum += output.iter().fold(0, |acc, digit| acc * 10 + digit);
+            let output = output
+                .iter()
+                .map(|&digit| scramble(rng, digit, &wiring))
+                .collect::<Vec<_>>();
+
+            input += &format!("{} | {}\n", patterns.join(" "), output.join(" "));
+        }
+
+        Generated {
+            input,
+            part_1: Some(easy_digits.into()),
+            part_2: Some(output_sum.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generate::check;
+
+    #[test]
+    fn generated_answers_match() {
+        for seed in 0..3 {
+            assert_eq!(check::<Day8>(seed, 10), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use std::{
+    collections::{hash_map::RandomState, HashMap, HashSet},
+    fmt::Display,
+};
+
+use crate::combinations::combinations;
+use common::{
+    grammar::{self, chars, lines, list, map, pair, verify},
+    trace, ParseError, Solution, Span,
+};
+
+mod combinations;
+mod generate;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Segment {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+}
+
+pub fn is_valid_digit(segments: &[Segment]) -> bool {
+    decode_digit(segments).is_some()
+}
+
+pub fn decode_digit(segments: &[Segment]) -> Option<usize> {
+    let segments_set: HashSet<&Segment, RandomState> = HashSet::from_iter(segments.iter());
+    use Segment::*;
+    let valid_combinations = vec![
+        (0, vec![A, B, C, E, F, G]),
+        (1, vec![C, F]),
+        (2, vec![A, C, D, E, G]),
+        (3, vec![A, C, D, F, G]),
+        (4, vec![B, C, D, F]),
+        (5, vec![A, B, D, F, G]),
+        (6, vec![A, B, D, E, F, G]),
+        (7, vec![A, C, F]),
+        (8, vec![A, B, C, D, E, F, G]),
+        (9, vec![A, B, C, D, F, G]),
+    ];
+
+    for (n, combination) in valid_combinations.iter() {
+        let combination_set: HashSet<&Segment, RandomState> =
+            HashSet::from_iter(combination.iter());
+        if combination_set == segments_set {
+            return Some(*n);
+        }
+    }
+    None
+}
+
+impl Segment {
+    pub fn from_char(source: char) -> Option<Self> {
+        match source {
+            'a' => Some(Self::A),
+            'b' => Some(Self::B),
+            'c' => Some(Self::C),
+            'd' => Some(Self::D),
+            'e' => Some(Self::E),
+            'f' => Some(Self::F),
+            'g' => Some(Self::G),
+            _ => None,
+        }
+    }
+
+    pub fn all_segments() -> impl Iterator<Item = Segment> {
+        ('a'..='g').filter_map(Segment::from_char)
+    }
+}
+
+impl Display for Segment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let idx = Segment::all_segments().position(|s| s == *self).unwrap();
+        write!(f, "{}", (b'a' + idx as u8) as char)
+    }
+}
+
+pub type SignalPattern = Vec<Segment>;
+
+fn pattern(source: Span) -> Result<SignalPattern, ParseError> {
+    verify(
+        chars("segment a-g", Segment::from_char),
+        "signal pattern",
+        |segments| !segments.is_empty(),
+    )(source)
+}
+
+pub struct Entry {
+    pub signal_patterns: Vec<SignalPattern>,
+    pub output_value: Vec<SignalPattern>,
+}
+
+impl Entry {
+    fn parse(source: Span) -> Result<Self, ParseError> {
+        let entry = pair(
+            " | ",
+            "`patterns | output` entry",
+            list(" ", pattern),
+            list(" ", pattern),
+        );
+        map(entry, |(signal_patterns, output_value)| Self {
+            signal_patterns,
+            output_value,
+        })(source)
+    }
+
+    pub fn flatten(&self) -> Vec<SignalPattern> {
+        let mut res = vec![];
+        for p in self.signal_patterns.iter() {
+            res.push(p.clone());
+        }
+        for p in self.output_value.iter() {
+            res.push(p.clone());
+        }
+        res
+    }
+}
+
+pub struct ConnectionMap {
+    map: HashMap<Segment, Segment>,
+}
+
+impl ConnectionMap {
+    pub fn decode(&self, value: Vec<Segment>) -> Option<usize> {
+        let mapped_segments = self.map_entry_value(value);
+        decode_digit(&mapped_segments)
+    }
+    pub fn map_entry_value(&self, in_value: Vec<Segment>) -> Vec<Segment> {
+        in_value
+            .iter()
+            .map(|in_segment| *self.map.get(in_segment).unwrap())
+            .collect()
+    }
+    pub fn from_entry(entry: &Entry) -> Self {
+        let entry_values = entry.flatten();
+        let possible_connections = combinations(Segment::all_segments().collect());
+
+        for (tried, possible_connection) in possible_connections.into_iter().enumerate() {
+            let possible_map = ConnectionMap::from_vec_tuple(possible_connection);
+            if entry_values
+                .iter()
+                .map(|entry_value| possible_map.map_entry_value(entry_value.clone()))
+                .all(|a| is_valid_digit(&a))
+            {
+                trace!(
+                    Debug,
+                    "wiring",
+                    "map {} fits after {} candidates",
+                    possible_map,
+                    tried + 1
+                );
+                return possible_map;
+            }
+        }
+
+        panic!("No possible connection map found for entry");
+    }
+
+    pub fn from_vec_tuple(source: Vec<(Segment, Segment)>) -> Self {
+        let mut map = HashMap::new();
+
+        for (from, to) in source.iter() {
+            map.insert(*from, *to);
+        }
+
+        ConnectionMap { map }
+    }
+}
+
+/// Where each wire goes, like `a=d b=e ...`.
+impl Display for ConnectionMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let wires = Segment::all_segments()
+            .filter_map(|from| self.map.get(&from).map(|to| format!("{}={}", from, to)))
+            .collect::<Vec<_>>();
+        write!(f, "{}", wires.join(" "))
+    }
+}
+
+pub fn part_1(entries: &[Entry]) -> usize {
+    entries
+        .iter()
+        .flat_map(|entry| entry.output_value.iter())
+        .filter(|segments| matches!(segments.len(), 2 | 3 | 4 | 7))
+        .count()
+}
+
+pub fn part_2(entries: &[Entry]) -> usize {
+    entries
+        .iter()
+        .enumerate()
+        .map(|(idx, e)| {
+            let map = ConnectionMap::from_entry(e);
+            let digits = e
+                .output_value
+                .iter()
+                .map(|d| format!("{}", map.decode(d.clone()).unwrap()))
+                .collect::<Vec<_>>()
+                .join("");
+            trace!(
+                Info,
+                "wiring",
+                "entry {} reads {} with map {}",
+                idx + 1,
+                digits,
+                map
+            );
+            digits.parse::<usize>().unwrap()
+        })
+        .sum()
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    const EXAMPLE: &'static str = include_str!("../example");
+
+    type Model = Vec<Entry>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Model, ParseError> {
+        grammar::parse(input, lines(Entry::parse))
+    }
+
+    fn part_1(entries: &Self::Model) -> Self::Part1 {
+        part_1(entries)
+    }
+
+    fn part_2(entries: &Self::Model) -> Self::Part2 {
+        part_2(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let entries = Day8::parse(Day8::EXAMPLE).unwrap();
+        assert_eq!(part_1(&entries), 26);
+        assert_eq!(part_2(&entries), 61229);
+    }
+
+    #[test]
+    fn reports_bad_segment() {
+        let input = "ab cd | ef\nabx | cd\n";
+        let err = Day8::parse(input).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: expected segment a-g, found \"x\""
+        );
+    }
+
+    #[test]
+    fn connection_map_from_entry() {
+        use Segment::*;
+        let entry = Entry::parse(Span::new(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf",
+        ))
+        .unwrap();
+        let map = ConnectionMap::from_entry(&entry);
+
+        let expected = [(D, A), (E, B), (A, C), (F, D), (G, E), (B, F), (C, G)];
+        for (from, to) in expected.iter() {
+            assert_eq!(map.map.get(from), Some(to));
+        }
+        let digits = entry
+            .output_value
+            .iter()
+            .map(|d| map.decode(d.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(digits, vec![Some(5), Some(3), Some(5), Some(3)]);
+    }
+}
@@ -0,0 +1,86 @@
+use common::generate::{Generated, Generator, Rng};
+use grid::Grid;
+
+use crate::Day9;
+
+/// Rows or columns that become ridges of 9s, at least one and never two side
+/// by side or on the edge.
+fn ridges(rng: &mut Rng, len: usize) -> Vec<bool> {
+    let mut ridge = vec![false; len];
+    let mut idx = rng.between(1, 6);
+    while idx < len - 1 {
+        ridge[idx] = true;
+        idx += rng.between(2, 8);
+    }
+    if !ridge.contains(&true) {
+        ridge[len / 2] = true;
+    }
+    ridge
+}
+
+/// Runs of positions between ridges, as `(start, len)`.
+fn spans(ridge: &[bool]) -> Vec<(usize, usize)> {
+    let mut spans = vec![];
+    let mut start = 0;
+    for (idx, &is_ridge) in ridge.iter().chain([true].iter()).enumerate() {
+        if is_ridge {
+            if idx > start {
+                spans.push((start, idx - start));
+            }
+            start = idx + 1;
+        }
+    }
+    spans
+}
+
+impl Generator for Day9 {
+    /// Width and height of the height map.
+    const DEFAULT_SIZE: usize = 100;
+
+    /// Ridges cut the map into rectangles. Each one is a basin that slopes
+    /// down to a single low point, so the risk levels and basin sizes are
+    /// known up front.
+    fn generate(rng: &mut Rng, size: usize) -> Generated {
+        let size = size.max(5);
+        let mut heights = Grid::filled(size, size, 9);
+        let rows = spans(&ridges(rng, size));
+        let cols = spans(&ridges(rng, size));
+
+        let mut risk = 0;
+        let mut basins = vec![];
+        for &(top, height) in rows.iter() {
+            for &(left, width) in cols.iter() {
+                let low = (left + rng.below(width), top + rng.below(height));
+                let depth = rng.between(0, 3);
+                for y in top..top + height {
+                    for x in left..left + width {
+                        let distance = x.abs_diff(low.0) + y.abs_diff(low.1);
+                        heights[(x, y)] = (depth + distance).min(8);
+                    }
+                }
+                risk += depth + 1;
+                basins.push(width * height);
+            }
+        }
+        basins.sort_unstable_by(|a, b| b.cmp(a));
+
+        Generated {
+            input: heights.to_string(),
+            part_1: Some(risk.into()),
+            part_2: Some(basins.iter().take(3).product::<usize>().into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::generate::check;
+
+    #[test]
+    fn generated_answers_match() {
+        for seed in 0..10 {
+            assert_eq!(check::<Day9>(seed, 30), Ok(()));
+        }
+    }
+}
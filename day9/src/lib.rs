@@ -0,0 +1,262 @@
+use common::{trace, ParseError, Solution, Span};
+use model::{map_neighbours, BasinPoint, HeightMap, Neighbours};
+use std::{cell::RefCell, collections::HashMap};
+
+mod generate;
+pub mod model;
+pub mod visual;
+
+fn group<T>(i: impl Iterator<Item = T>) -> HashMap<T, Vec<T>>
+where
+    T: core::hash::Hash + Clone + Copy + Eq,
+{
+    let mut map = HashMap::new();
+    for item in i {
+        let entry = map.entry(item);
+        entry
+            .and_modify(|items: &mut Vec<T>| items.push(item))
+            .or_insert(vec![item]);
+    }
+    map
+}
+
+pub fn part_1(map: &HeightMap<usize>) -> usize {
+    let low_points = map_neighbours(map, |n: Neighbours<usize>| {
+        if n.is_low_point() {
+            Some(n.value)
+        } else {
+            None
+        }
+    });
+    trace!(
+        Info,
+        "basins",
+        "{} low points",
+        low_points.iter().filter(|v| v.is_some()).count()
+    );
+    low_points
+        .iter()
+        .map(|v| v.map(|v| v + 1).unwrap_or(0))
+        .sum()
+}
+
+/// Marks every low point as the seed of its own basin.
+pub(crate) fn mark_basins(map: &HeightMap<usize>) -> HeightMap<BasinPoint> {
+    let next_id = RefCell::new(0);
+
+    map_neighbours(map, |n: Neighbours<usize>| {
+        if n.is_low_point() {
+            let mut next_idx = next_id.borrow_mut();
+            let basin_id = *next_idx;
+            *next_idx = basin_id + 1;
+            BasinPoint::Marked { basin_id }
+        } else {
+            if n.value == 9 {
+                BasinPoint::Ridge
+            } else {
+                BasinPoint::Unmarked
+            }
+        }
+    })
+}
+
+pub(crate) fn is_filled(basin_map: &HeightMap<BasinPoint>) -> bool {
+    basin_map.iter().all(|p| p.is_marked() || p.is_ridge())
+}
+
+/// Grows every marked basin by one point in each direction.
+pub(crate) fn grow(basin_map: &HeightMap<BasinPoint>) -> HeightMap<BasinPoint> {
+    map_neighbours(basin_map, |n| {
+        let marked = [n.top, n.left, n.right, n.bottom]
+            .iter()
+            .find_map(|&point| point.filter(|p| p.is_marked()));
+
+        match marked {
+            Some(BasinPoint::Marked { basin_id, .. }) => n.value.mark(basin_id),
+            _ => n.value,
+        }
+    })
+}
+
+pub fn part_2(map: &HeightMap<usize>) -> usize {
+    let mut basin_map = mark_basins(map);
+    let mut passes = 0;
+    while !is_filled(&basin_map) {
+        basin_map = grow(&basin_map);
+        passes += 1;
+        trace!(
+            Debug,
+            "basins",
+            "pass {}: {} points left to mark",
+            passes,
+            basin_map
+                .iter()
+                .filter(|p| !p.is_marked() && !p.is_ridge())
+                .count()
+        );
+    }
+    trace!(Info, "basins", "filled after {} passes", passes);
+
+    // find largest basins
+    let basin_ids = basin_map.iter().filter_map(|p| match p {
+        BasinPoint::Marked { basin_id, .. } => Some(basin_id),
+        _ => None,
+    });
+    let grouped = group(basin_ids);
+    let mut basins = grouped
+        .iter()
+        .map(|(&id, v)| (*id, v.len()))
+        .collect::<Vec<_>>();
+    basins.sort_by_key(|(_, l)| *l);
+
+    let mut biggest = basins.iter().rev();
+    let a = biggest.next().unwrap().1;
+    let b = biggest.next().unwrap().1;
+    let c = biggest.next().unwrap().1;
+    trace!(Info, "basins", "largest basins hold {}, {} and {}", a, b, c);
+
+    a * b * c
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    const EXAMPLE: &'static str = include_str!("../example");
+
+    type Model = HeightMap<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Model, ParseError> {
+        model::parse(Span::new(input))
+    }
+
+    fn part_1(map: &Self::Model) -> Self::Part1 {
+        part_1(map)
+    }
+
+    fn part_2(map: &Self::Model) -> Self::Part2 {
+        part_2(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{generate::Rng, property};
+    use std::collections::HashSet;
+
+    type Heights = Vec<Vec<usize>>;
+
+    fn neighbours(heights: &Heights, (x, y): (usize, usize)) -> Vec<(usize, usize)> {
+        let mut neighbours = vec![];
+        if y > 0 {
+            neighbours.push((x, y - 1));
+        }
+        if x > 0 {
+            neighbours.push((x - 1, y));
+        }
+        if x + 1 < heights[0].len() {
+            neighbours.push((x + 1, y));
+        }
+        if y + 1 < heights.len() {
+            neighbours.push((x, y + 1));
+        }
+        neighbours
+    }
+
+    fn low_points(heights: &Heights) -> Vec<(usize, usize)> {
+        let mut low_points = vec![];
+        for y in 0..heights.len() {
+            for x in 0..heights[0].len() {
+                let height = heights[y][x];
+                if neighbours(heights, (x, y))
+                    .iter()
+                    .all(|&(nx, ny)| heights[ny][nx] > height)
+                {
+                    low_points.push((x, y));
+                }
+            }
+        }
+        low_points
+    }
+
+    /// Flood fills every area between 9s, returning their sizes, or `None`
+    /// if some area doesn't hold exactly one low point and so isn't a basin.
+    fn naive_basins(heights: &Heights) -> Option<Vec<usize>> {
+        let low_points = low_points(heights);
+        let mut seen = HashSet::new();
+        let mut basins = vec![];
+        for y in 0..heights.len() {
+            for x in 0..heights[0].len() {
+                if heights[y][x] == 9 || seen.contains(&(x, y)) {
+                    continue;
+                }
+                let mut area = vec![(x, y)];
+                seen.insert((x, y));
+                let mut idx = 0;
+                while idx < area.len() {
+                    for (nx, ny) in neighbours(heights, area[idx]) {
+                        if heights[ny][nx] != 9 && seen.insert((nx, ny)) {
+                            area.push((nx, ny));
+                        }
+                    }
+                    idx += 1;
+                }
+                if area.iter().filter(|p| low_points.contains(p)).count() != 1 {
+                    return None;
+                }
+                basins.push(area.len());
+            }
+        }
+        Some(basins)
+    }
+
+    /// A small map with plenty of 9s, so it splits into several basins.
+    fn random_heights(rng: &mut Rng) -> Heights {
+        let (width, height) = (rng.between(2, 8), rng.between(2, 8));
+        (0..height)
+            .map(|_| {
+                (0..width)
+                    .map(|_| if rng.chance(1, 3) { 9 } else { rng.below(9) })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn example() {
+        let map = Day9::parse(Day9::EXAMPLE).unwrap();
+        assert_eq!(part_1(&map), 15);
+        assert_eq!(part_2(&map), 1134);
+    }
+
+    #[test]
+    fn matches_naive_solution() {
+        property::check(200, |rng| {
+            let (heights, mut basins) = loop {
+                let heights = random_heights(rng);
+                match naive_basins(&heights) {
+                    Some(basins) if basins.len() >= 3 => break (heights, basins),
+                    _ => {}
+                }
+            };
+            let input = heights
+                .iter()
+                .map(|row| row.iter().map(|h| h.to_string()).collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let map = Day9::parse(&input).unwrap();
+
+            let risk = low_points(&heights)
+                .iter()
+                .map(|&(x, y)| heights[y][x] + 1)
+                .sum::<usize>();
+            assert_eq!(part_1(&map), risk);
+
+            basins.sort_unstable();
+            assert_eq!(part_2(&map), basins.iter().rev().take(3).product::<usize>());
+        });
+    }
+}
@@ -0,0 +1,81 @@
+use grid::{
+    image::Rgb,
+    visual::{Cell, Scene, Visual, PALETTE},
+    Grid,
+};
+
+use crate::{
+    grow, is_filled, mark_basins,
+    model::{BasinPoint, HeightMap},
+    Day9,
+};
+
+/// Grows the basins outwards from their low points, one pass per frame.
+pub struct Basins {
+    heights: HeightMap<usize>,
+    basins: HeightMap<BasinPoint>,
+    passes: usize,
+}
+
+impl Basins {
+    pub fn new(heights: HeightMap<usize>) -> Self {
+        let basins = mark_basins(&heights);
+        Basins {
+            heights,
+            basins,
+            passes: 0,
+        }
+    }
+}
+
+impl Scene for Basins {
+    fn frame(&self) -> Grid<Cell> {
+        self.basins.map(|pos, point| match point {
+            BasinPoint::Marked { basin_id } => Cell::new('█', PALETTE[basin_id % PALETTE.len()]),
+            BasinPoint::Ridge => Cell::new('█', 236),
+            BasinPoint::Unmarked => {
+                let digit = std::char::from_digit(self.heights[pos] as u32, 10).unwrap_or('?');
+                Cell::new(digit, 242)
+            }
+        })
+    }
+
+    fn advance(&mut self) -> bool {
+        if is_filled(&self.basins) {
+            return false;
+        }
+        self.basins = grow(&self.basins);
+        self.passes += 1;
+        true
+    }
+
+    fn status(&self) -> String {
+        let unmarked = self
+            .basins
+            .iter()
+            .filter(|p| !p.is_marked() && !p.is_ridge())
+            .count();
+        format!("pass {}, {} points left to fill", self.passes, unmarked)
+    }
+}
+
+impl Visual for Day9 {
+    type Scene = Basins;
+
+    fn scene(heights: Self::Model) -> Self::Scene {
+        Basins::new(heights)
+    }
+
+    /// Every basin in its own colour, with the ridges between them grey.
+    fn image(heights: &Self::Model) -> Grid<Rgb> {
+        let mut basins = mark_basins(heights);
+        while !is_filled(&basins) {
+            basins = grow(&basins);
+        }
+        basins.map(|_, point| match point {
+            BasinPoint::Marked { basin_id } => Rgb::distinct(*basin_id),
+            BasinPoint::Ridge => Rgb::grey(48),
+            BasinPoint::Unmarked => Rgb::BLACK,
+        })
+    }
+}
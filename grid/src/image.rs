@@ -0,0 +1,191 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::Grid;
+
+/// Smallest side, in pixels, that exported images are scaled up to.
+const MIN_SIZE: usize = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    pub fn grey(level: u8) -> Self {
+        Rgb(level, level, level)
+    }
+
+    /// Black through red and yellow to white as `value` goes from 0 to `max`.
+    pub fn heat(value: usize, max: usize) -> Self {
+        let level = (value.min(max) * 765 / max.max(1)) as u32;
+        let channel = |offset: u32| level.saturating_sub(offset).min(255) as u8;
+        Rgb(channel(0), channel(255), channel(510))
+    }
+
+    /// The `idx`th of a series of colours that are far apart in hue.
+    pub fn distinct(idx: usize) -> Self {
+        let hue = (idx as f64 * 137.508) % 360.0 / 60.0;
+        let rising = hue % 1.0;
+        let (r, g, b) = match hue as usize {
+            0 => (1.0, rising, 0.0),
+            1 => (1.0 - rising, 1.0, 0.0),
+            2 => (0.0, 1.0, rising),
+            3 => (0.0, 1.0 - rising, 1.0),
+            4 => (rising, 0.0, 1.0),
+            _ => (1.0, 0.0, 1.0 - rising),
+        };
+        let channel = |c: f64| (55.0 + c * 200.0) as u8;
+        Rgb(channel(r), channel(g), channel(b))
+    }
+
+    fn luminance(&self) -> u8 {
+        ((299 * self.0 as u32 + 587 * self.1 as u32 + 114 * self.2 as u32) / 1000) as u8
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Pgm,
+    Ppm,
+    Svg,
+}
+
+impl Format {
+    /// Picks the format from the file extension.
+    pub fn of(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "pgm" => Some(Format::Pgm),
+            "ppm" => Some(Format::Ppm),
+            "svg" => Some(Format::Svg),
+            _ => None,
+        }
+    }
+}
+
+fn scale(image: &Grid<Rgb>) -> usize {
+    (MIN_SIZE / image.width().max(image.height()).max(1)).max(1)
+}
+
+/// Binary PGM or PPM, each cell blown up to a `scale` pixel square.
+fn write_pixels(out: &mut impl Write, image: &Grid<Rgb>, colour: bool) -> io::Result<()> {
+    let scale = scale(image);
+    let magic = if colour { "P6" } else { "P5" };
+    write!(
+        out,
+        "{}\n{} {}\n255\n",
+        magic,
+        image.width() * scale,
+        image.height() * scale
+    )?;
+    let mut row = Vec::new();
+    for cells in image.rows() {
+        row.clear();
+        for rgb in cells {
+            for _ in 0..scale {
+                if colour {
+                    row.extend([rgb.0, rgb.1, rgb.2]);
+                } else {
+                    row.push(rgb.luminance());
+                }
+            }
+        }
+        for _ in 0..scale {
+            out.write_all(&row)?;
+        }
+    }
+    Ok(())
+}
+
+/// One unit square per cell on a black background; black cells are left out.
+fn write_svg(out: &mut impl Write, image: &Grid<Rgb>) -> io::Result<()> {
+    let scale = scale(image);
+    let (width, height) = (image.width(), image.height());
+    writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">",
+        width * scale,
+        height * scale,
+        width,
+        height
+    )?;
+    writeln!(
+        out,
+        "<rect width=\"{}\" height=\"{}\" fill=\"#000000\"/>",
+        width, height
+    )?;
+    for ((x, y), rgb) in image.enumerate().filter(|(_, &rgb)| rgb != Rgb::BLACK) {
+        writeln!(
+            out,
+            "<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"#{:02x}{:02x}{:02x}\"/>",
+            x, y, rgb.0, rgb.1, rgb.2
+        )?;
+    }
+    writeln!(out, "</svg>")
+}
+
+pub fn write(out: &mut impl Write, format: Format, image: &Grid<Rgb>) -> io::Result<()> {
+    match format {
+        Format::Pgm => write_pixels(out, image, false),
+        Format::Ppm => write_pixels(out, image, true),
+        Format::Svg => write_svg(out, image),
+    }
+}
+
+/// Writes `image` to `path` in the format its extension asks for.
+pub fn save(path: &Path, image: &Grid<Rgb>) -> io::Result<()> {
+    let format = Format::of(path).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "expected a .pgm, .ppm or .svg file",
+        )
+    })?;
+    let mut out = BufWriter::new(File::create(path)?);
+    write(&mut out, format, image)?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Grid<Rgb> {
+        Grid::from_fn(MIN_SIZE / 2, 1, |(x, _)| {
+            if x % 2 == 0 {
+                Rgb::WHITE
+            } else {
+                Rgb::BLACK
+            }
+        })
+    }
+
+    #[test]
+    fn pixels_are_scaled() {
+        let mut out = Vec::new();
+        write(&mut out, Format::Pgm, &checkerboard()).unwrap();
+        let header = format!("P5\n{} 2\n255\n", MIN_SIZE);
+        assert!(out.starts_with(header.as_bytes()));
+        assert_eq!(&out[header.len()..header.len() + 4], &[255, 255, 0, 0]);
+        assert_eq!(out.len(), header.len() + MIN_SIZE * 2);
+    }
+
+    #[test]
+    fn svg_skips_black_cells() {
+        let mut out = Vec::new();
+        write(&mut out, Format::Svg, &checkerboard()).unwrap();
+        let svg = String::from_utf8(out).unwrap();
+        assert_eq!(svg.matches("fill=\"#ffffff\"").count(), MIN_SIZE / 4);
+        assert!(svg.contains("viewBox=\"0 0 256 1\""));
+    }
+
+    #[test]
+    fn heat_runs_from_black_to_white() {
+        assert_eq!(Rgb::heat(0, 3), Rgb::BLACK);
+        assert_eq!(Rgb::heat(1, 3), Rgb(255, 0, 0));
+        assert_eq!(Rgb::heat(3, 3), Rgb::WHITE);
+    }
+}
@@ -0,0 +1,284 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use common::{ParseError, Span};
+
+pub mod image;
+pub mod visual;
+
+/// `(x, y)` with the origin in the top left corner.
+pub type Pos = (usize, usize);
+
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const ADJACENT: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+fn step((x, y): Pos, (dx, dy): (isize, isize), width: usize, height: usize) -> Option<Pos> {
+    let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+    (pos.0 < width && pos.1 < height).then_some(pos)
+}
+
+/// Rectangular grid stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.1 * self.width + pos.0])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.1 * self.width + pos.0])
+        } else {
+            None
+        }
+    }
+
+    /// The position `(dx, dy)` away from `pos`, if it is still on the grid.
+    pub fn offset(&self, pos: Pos, delta: (isize, isize)) -> Option<Pos> {
+        step(pos, delta, self.width, self.height)
+    }
+
+    /// Up, left, right and down, skipping the ones off the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        let (width, height) = (self.width, self.height);
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |delta| step(pos, delta, width, height))
+    }
+
+    /// All eight surrounding positions, skipping the ones off the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        let (width, height) = (self.width, self.height);
+        ADJACENT
+            .into_iter()
+            .filter_map(move |delta| step(pos, delta, width, height))
+    }
+
+    /// Every position in row order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |idx| (idx % width, idx / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn enumerate(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(Pos, &T) -> U) -> Grid<U> {
+        Grid::from_fn(self.width, self.height, |pos| f(pos, &self[pos]))
+    }
+
+    /// Builds a grid from character art, one row per line. Every row must be
+    /// as long as the first.
+    pub fn parse_chars(
+        input: Span,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for line in input.lines() {
+            let before = cells.len();
+            for (c, span) in line.chars() {
+                cells.push(cell(c).ok_or_else(|| span.error(expected))?);
+            }
+            let row_len = cells.len() - before;
+            match width {
+                None => width = Some(row_len),
+                Some(width) if width != row_len => {
+                    return Err(line.error(&format!("row of {} cells", width)));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(input.error("at least one row")),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    /// Quarter turn clockwise.
+    pub fn rotate(&self) -> Self {
+        let height = self.height;
+        Grid::from_fn(height, self.width, |(x, y)| {
+            self[(y, height - 1 - x)].clone()
+        })
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let width = self.width;
+        self.map(|(x, y), _| self[(width - 1 - x, y)].clone())
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let height = self.height;
+        self.map(|(x, y), _| self[(x, height - 1 - y)].clone())
+    }
+}
+
+impl<T: From<u8>> Grid<T> {
+    pub fn parse_digits(input: Span) -> Result<Self, ParseError> {
+        Self::parse_chars(input, "digit", |c| c.to_digit(10).map(|d| T::from(d as u8)))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", pos))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(art: &str) -> Grid<char> {
+        Grid::parse_chars(Span::new(art), "cell", Some).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        let digits = Grid::<u8>::parse_digits(Span::new("123\n456\n")).unwrap();
+        assert_eq!((digits.width(), digits.height()), (3, 2));
+        assert_eq!(digits[(2, 1)], 6);
+        assert_eq!(digits.get((3, 0)), None);
+        assert_eq!(digits.to_string(), "123\n456\n");
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows() {
+        let err = Grid::<u8>::parse_digits(Span::new("123\n45\n")).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        let err = Grid::<u8>::parse_digits(Span::new("12\n4x\n")).unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 2, "x"));
+    }
+
+    #[test]
+    fn neighbours_stay_on_grid() {
+        let g = grid("abc\ndef\nghi\n");
+        assert_eq!(g.neighbours4((0, 0)).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(g.neighbours8((0, 0)).count(), 3);
+        assert_eq!(g.neighbours4((1, 1)).count(), 4);
+        assert_eq!(g.neighbours8((1, 1)).count(), 8);
+        assert_eq!(g.neighbours8((2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn transformations() {
+        let g = grid("abc\ndef\n");
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(g.rotate().to_string(), "da\neb\nfc\n");
+        assert_eq!(g.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(g.flip_vertical().to_string(), "def\nabc\n");
+        assert_eq!(g.rotate().rotate().rotate().rotate(), g);
+    }
+}
@@ -0,0 +1,266 @@
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{self, Read, Write},
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use common::{
+    cli::{self, Args, Source},
+    Solution,
+};
+
+use crate::{
+    image::{self, Rgb},
+    Grid,
+};
+
+/// One character on screen, with an optional 256-colour foreground.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub colour: Option<u8>,
+}
+
+impl Cell {
+    pub const EMPTY: Cell = Cell {
+        glyph: ' ',
+        colour: None,
+    };
+
+    pub fn new(glyph: char, colour: u8) -> Self {
+        Cell {
+            glyph,
+            colour: Some(colour),
+        }
+    }
+}
+
+/// Colours that are easy to tell apart, for things like basin ids.
+pub const PALETTE: [u8; 12] = [196, 208, 226, 46, 51, 33, 129, 201, 160, 28, 94, 244];
+
+/// Something that can be watched one frame at a time.
+pub trait Scene {
+    fn frame(&self) -> Grid<Cell>;
+
+    /// Moves on to the next frame; false once there is nothing left to show.
+    fn advance(&mut self) -> bool;
+
+    /// A line of progress information shown under the frame.
+    fn status(&self) -> String;
+}
+
+const HELP: &str = "space play/pause  n step  +/- speed  q quit";
+
+/// Puts the terminal in raw mode for as long as it lives.
+struct Terminal {
+    saved: String,
+}
+
+impl Terminal {
+    fn stty(args: &[&str]) -> Option<String> {
+        let output = Command::new("stty")
+            .args(args)
+            .stdin(File::open("/dev/tty").ok()?)
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn open() -> Option<Self> {
+        let saved = Self::stty(&["-g"])?;
+        Self::stty(&["-icanon", "-echo", "min", "1"])?;
+        print!("\x1b[?1049h\x1b[?25l");
+        Some(Terminal { saved })
+    }
+
+    /// Rows and columns, if the terminal knows.
+    fn size() -> Option<(usize, usize)> {
+        let size = Self::stty(&["size"])?;
+        let (rows, cols) = size.split_once(' ')?;
+        Some((rows.parse().ok()?, cols.parse().ok()?))
+    }
+
+    fn keys() -> Option<Receiver<u8>> {
+        let mut tty = File::open("/dev/tty").ok()?;
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let mut key = [0];
+            while tty.read_exact(&mut key).is_ok() && sender.send(key[0]).is_ok() {}
+        });
+        Some(receiver)
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+        Self::stty(&[&self.saved]);
+    }
+}
+
+/// Shrinks `frame` to fit in `rows` by `cols`, keeping the first visible cell
+/// of every block so thin lines and single dots don't vanish.
+fn fit(frame: &Grid<Cell>, rows: usize, cols: usize) -> Grid<Cell> {
+    let scale = frame
+        .width()
+        .div_ceil(cols)
+        .max(frame.height().div_ceil(rows))
+        .max(1);
+    if scale == 1 {
+        return frame.clone();
+    }
+    let width = frame.width().div_ceil(scale);
+    let height = frame.height().div_ceil(scale);
+    Grid::from_fn(width, height, |(x, y)| {
+        let block = (y * scale..(y * scale + scale).min(frame.height()))
+            .flat_map(|y| (x * scale..(x * scale + scale).min(frame.width())).map(move |x| (x, y)));
+        block
+            .map(|pos| frame[pos])
+            .find(|cell| cell.glyph != ' ')
+            .unwrap_or(Cell::EMPTY)
+    })
+}
+
+fn render(frame: &Grid<Cell>, status: &str, footer: &str) -> String {
+    let mut screen = String::from("\x1b[H");
+    let mut colour = None;
+    for row in frame.rows() {
+        for cell in row {
+            if cell.colour != colour && cell.glyph != ' ' {
+                match cell.colour {
+                    Some(code) => write!(screen, "\x1b[38;5;{}m", code).unwrap(),
+                    None => screen.push_str("\x1b[0m"),
+                }
+                colour = cell.colour;
+            }
+            screen.push(cell.glyph);
+        }
+        screen.push_str("\x1b[K\n");
+    }
+    write!(screen, "\x1b[0m{}\x1b[K\n{}\x1b[K\x1b[J", status, footer).unwrap();
+    screen
+}
+
+/// Plays `scene` in the terminal until it is over and the user quits. When
+/// there is no terminal to read keys from it simply plays through once.
+pub fn play(scene: &mut dyn Scene) {
+    let terminal = Terminal::open();
+    let keys = terminal.as_ref().and_then(|_| Terminal::keys());
+    let (rows, cols) = Terminal::size().unwrap_or((24, 80));
+    let mut fps = 10u32;
+    let mut playing = true;
+    let mut finished = false;
+
+    loop {
+        let footer = match (&keys, finished, playing) {
+            (None, ..) => String::new(),
+            (_, true, _) => format!("done  {}", HELP),
+            (_, _, true) => format!("playing at {} fps  {}", fps, HELP),
+            _ => format!("paused  {}", HELP),
+        };
+        let frame = fit(&scene.frame(), rows.saturating_sub(3).max(1), cols.max(1));
+        print!("{}", render(&frame, &scene.status(), &footer));
+        let _ = io::stdout().flush();
+
+        let delay = Duration::from_secs(1) / fps;
+        let key = match &keys {
+            None if finished => break,
+            None => {
+                thread::sleep(delay);
+                None
+            }
+            Some(keys) if playing && !finished => match keys.recv_timeout(delay) {
+                Ok(key) => Some(key),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => break,
+            },
+            Some(keys) => match keys.recv() {
+                Ok(key) => Some(key),
+                Err(_) => break,
+            },
+        };
+
+        match key {
+            None => finished = !scene.advance(),
+            Some(b' ') => playing = !playing,
+            Some(b'n') | Some(b'.') if !finished => {
+                playing = false;
+                finished = !scene.advance();
+            }
+            Some(b'+') | Some(b'=') => fps = (fps * 2).min(1000),
+            Some(b'-') => fps = (fps / 2).max(1),
+            Some(b'q') | Some(3) => break,
+            Some(_) => {}
+        }
+    }
+    drop(terminal);
+}
+
+/// Days whose state can be watched in the terminal or saved as an image.
+pub trait Visual: Solution {
+    type Scene: Scene;
+
+    fn scene(model: Self::Model) -> Self::Scene;
+
+    /// The state worth keeping a picture of, usually once solved.
+    fn image(model: &Self::Model) -> Grid<Rgb>;
+}
+
+/// Entry point for days that can be watched: `--visualize` plays the scene
+/// built from the parsed input, `--export <file>` writes its image, anything
+/// else runs the solution as usual.
+pub fn main<S: Visual>() {
+    main_or::<S>(common::main::<S>)
+}
+
+/// Like `main`, but hands anything other than `--visualize` and `--export`
+/// over to `fallback`.
+pub fn main_or<S: Visual>(fallback: fn()) {
+    let mut args = Args::from_env();
+    let visualize = args.flag("--visualize");
+    let export = args.option("--export").map(PathBuf::from);
+    if !visualize && export.is_none() {
+        return fallback();
+    }
+    let source = Source::from_args(&mut args);
+    args.finish();
+    if let Some(path) = &export {
+        if image::Format::of(path).is_none() {
+            cli::usage_error("--export needs a .pgm, .ppm or .svg file");
+        }
+    }
+
+    let model = common::load::<S>(&source);
+    if let Some(path) = export {
+        image::save(&path, &S::image(&model))
+            .unwrap_or_else(|err| cli::fail(format!("cannot write {}: {}", path.display(), err)));
+    }
+    if visualize {
+        play(&mut S::scene(model));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_keeps_sparse_dots() {
+        let mut frame = Grid::filled(8, 4, Cell::EMPTY);
+        frame[(7, 3)] = Cell::new('#', 1);
+        let small = fit(&frame, 2, 4);
+        assert_eq!((small.width(), small.height()), (4, 2));
+        assert_eq!(small[(3, 1)], Cell::new('#', 1));
+        assert_eq!(small.iter().filter(|cell| cell.glyph != ' ').count(), 1);
+    }
+}